@@ -0,0 +1,127 @@
+use std::fmt::Debug;
+use std::sync::Arc;
+
+use parking_lot::Mutex;
+
+use crate::{CompositeMatcher, Matcher};
+
+/// Records arguments passed in the position of its matcher.
+/// Clones share the recorded values, so it can be moved to other threads.
+///
+/// ```
+/// let captor = mry::Captor::<String>::new();
+/// let matcher: mry::Matcher<String> = captor.matcher();
+/// ```
+pub struct Captor<T> {
+    values: Arc<Mutex<Vec<T>>>,
+}
+
+impl<T> Captor<T> {
+    pub fn new() -> Self {
+        Self {
+            values: Default::default(),
+        }
+    }
+}
+
+impl<T> Default for Captor<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Clone for Captor<T> {
+    fn clone(&self) -> Self {
+        Self {
+            values: self.values.clone(),
+        }
+    }
+}
+
+impl<T: Debug> Debug for Captor<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Captor").field(&*self.values.lock()).finish()
+    }
+}
+
+impl<T: Clone + Send + Sync + 'static> Captor<T> {
+    /// Returns a matcher that matches any value and records it when a rule with the matcher serves the call.
+    pub fn matcher(&self) -> Matcher<T> {
+        Matcher::Composite(Box::new(CaptorMatcher(self.clone())))
+    }
+
+    /// Returns the last recorded value.
+    pub fn last(&self) -> Option<T> {
+        self.values.lock().last().cloned()
+    }
+
+    /// Returns the n-th recorded value.
+    pub fn nth(&self, n: usize) -> Option<T> {
+        self.values.lock().get(n).cloned()
+    }
+
+    /// Returns all recorded values in the order they were recorded.
+    pub fn all(&self) -> Vec<T> {
+        self.values.lock().clone()
+    }
+}
+
+struct CaptorMatcher<T>(Captor<T>);
+
+impl<T> Debug for CaptorMatcher<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Captor")
+    }
+}
+
+impl<T: Clone> CompositeMatcher<T> for CaptorMatcher<T> {
+    fn matches(&self, _input: &T) -> bool {
+        true
+    }
+
+    fn capture(&self, input: &T) {
+        self.0.values.lock().push(input.clone());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn captures() {
+        let captor = Captor::<u8>::new();
+        let matcher = captor.matcher();
+        assert!(matcher.matches(&1));
+        matcher.capture(&1);
+        matcher.capture(&2);
+
+        assert_eq!(captor.all(), vec![1, 2]);
+        assert_eq!(captor.last(), Some(2));
+        assert_eq!(captor.nth(0), Some(1));
+        assert_eq!(captor.nth(2), None);
+    }
+
+    #[test]
+    fn does_not_capture_unless_whole_call_matches() {
+        let captor = Captor::<u8>::new();
+        let matcher: Matcher<(u8, u8)> = (captor.matcher(), Matcher::eq(2)).into();
+        let mut rule = crate::Rule::new(matcher, crate::Behavior2::from(|a, b| a + b).into());
+
+        rule.called(&mut (1, 3));
+        rule.called(&mut (4, 2));
+
+        assert_eq!(captor.all(), vec![4]);
+    }
+
+    #[test]
+    fn clones_share_values() {
+        let captor = Captor::<u8>::new();
+        let matcher = captor.clone().matcher();
+        std::thread::spawn(move || matcher.capture(&1))
+            .join()
+            .unwrap();
+
+        assert_eq!(captor.all(), vec![1]);
+    }
+}
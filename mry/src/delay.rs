@@ -0,0 +1,71 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll, Waker};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use parking_lot::Mutex;
+
+/// A runtime-agnostic future that completes after the duration.
+/// A thread is spawned on the first poll to wake the task at the deadline.
+pub(crate) struct Delay {
+    deadline: Instant,
+    waker: Option<Arc<Mutex<Option<Waker>>>>,
+}
+
+impl Delay {
+    pub fn new(duration: Duration) -> Self {
+        Self {
+            deadline: Instant::now() + duration,
+            waker: None,
+        }
+    }
+}
+
+impl Future for Delay {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let now = Instant::now();
+        if now >= self.deadline {
+            return Poll::Ready(());
+        }
+        match &self.waker {
+            // Polled again before the deadline, so keep the latest waker.
+            Some(waker) => *waker.lock() = Some(cx.waker().clone()),
+            None => {
+                let waker = Arc::new(Mutex::new(Some(cx.waker().clone())));
+                let timeout = self.deadline - now;
+                let thread_waker = waker.clone();
+                thread::spawn(move || {
+                    thread::sleep(timeout);
+                    if let Some(waker) = thread_waker.lock().take() {
+                        waker.wake();
+                    }
+                });
+                self.waker = Some(waker);
+            }
+        }
+        Poll::Pending
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn completes_after_duration() {
+        let start = Instant::now();
+
+        async_std::task::block_on(Delay::new(Duration::from_millis(50)));
+
+        assert!(start.elapsed() >= Duration::from_millis(50));
+    }
+
+    #[test]
+    fn zero_duration_is_ready() {
+        async_std::task::block_on(Delay::new(Duration::ZERO));
+    }
+}
@@ -0,0 +1,122 @@
+use std::cell::Cell;
+use std::marker::PhantomData;
+
+/// What a mock does for a call that no rule matches
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fallback {
+    /// Panics with "mock not found". This is the default.
+    Strict,
+    /// Returns `Default::default()`
+    Nice,
+    /// Calls the real implementation
+    RealImpl,
+}
+
+thread_local! {
+    static DEFAULT_FALLBACK: Cell<Option<Fallback>> = const { Cell::new(None) };
+}
+
+/// Sets the fallback of mocks without one in the current thread until the guard is dropped.
+/// This is for setting a fallback per test.
+pub fn default_fallback(fallback: Fallback) -> FallbackGuard {
+    FallbackGuard {
+        previous: DEFAULT_FALLBACK.with(|cell| cell.replace(Some(fallback))),
+        _not_send: PhantomData,
+    }
+}
+
+pub(crate) fn current_default_fallback() -> Option<Fallback> {
+    DEFAULT_FALLBACK.with(|cell| cell.get())
+}
+
+/// Guard returned by `default_fallback`, which restores the previous fallback on drop
+#[must_use = "the fallback is restored when the guard is dropped"]
+pub struct FallbackGuard {
+    previous: Option<Fallback>,
+    // The fallback is set for the current thread
+    _not_send: PhantomData<*const ()>,
+}
+
+impl Drop for FallbackGuard {
+    fn drop(&mut self) {
+        DEFAULT_FALLBACK.with(|cell| cell.set(self.previous));
+    }
+}
+
+/// Finds `Default::default` of a type if it implements `Default`, used by generated code.
+#[doc(hidden)]
+pub struct DefaultOutput<O>(PhantomData<fn() -> O>);
+
+impl<O> Default for DefaultOutput<O> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+#[doc(hidden)]
+pub trait DefaultOutputFound<O> {
+    fn default_output(&self) -> Option<fn() -> O>;
+}
+
+impl<O: Default> DefaultOutputFound<O> for DefaultOutput<O> {
+    fn default_output(&self) -> Option<fn() -> O> {
+        Some(O::default)
+    }
+}
+
+/// Used by autoref if the type doesn't implement `Default`.
+#[doc(hidden)]
+pub trait DefaultOutputNotFound<O> {
+    fn default_output(&self) -> Option<fn() -> O>;
+}
+
+impl<O> DefaultOutputNotFound<O> for &DefaultOutput<O> {
+    fn default_output(&self) -> Option<fn() -> O> {
+        None
+    }
+}
+
+/// Returns `Some(Default::default)` of the type if it implements `Default`, or `None`, used by generated code.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! default_output {
+    ($ty:ty) => {{
+        #[allow(unused_imports)]
+        use $crate::{DefaultOutputFound as _, DefaultOutputNotFound as _};
+        (&$crate::DefaultOutput::<$ty>::default()).default_output()
+    }};
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    struct NotDefault;
+
+    #[test]
+    fn default_fallback_is_restored() {
+        assert_eq!(current_default_fallback(), None);
+        {
+            let _guard = default_fallback(Fallback::Nice);
+            assert_eq!(current_default_fallback(), Some(Fallback::Nice));
+            {
+                let _guard = default_fallback(Fallback::RealImpl);
+                assert_eq!(current_default_fallback(), Some(Fallback::RealImpl));
+            }
+            assert_eq!(current_default_fallback(), Some(Fallback::Nice));
+        }
+        assert_eq!(current_default_fallback(), None);
+    }
+
+    #[test]
+    fn default_output_found() {
+        let default = crate::default_output!(String);
+        assert_eq!(default.map(|default| default()), Some(String::new()));
+    }
+
+    #[test]
+    fn default_output_not_found() {
+        let default = crate::default_output!(NotDefault);
+        assert!(default.is_none());
+    }
+}
@@ -0,0 +1,105 @@
+use std::fmt::Debug;
+
+use crate::{Behavior, MockLocator};
+
+/// Verifies the order of calls across methods, objects, and static functions.
+#[derive(Default)]
+pub struct InOrder {
+    steps: Vec<Step>,
+}
+
+struct Step {
+    description: String,
+    sequences: Vec<u64>,
+}
+
+impl InOrder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds calls matching the mock as the next step.
+    pub fn then<I, O, B, C>(mut self, mut mock: MockLocator<'_, I, O, B, C>) -> Self
+    where
+        I: Clone + Debug + Send + Sync + 'static,
+        O: Debug + Send + Sync + 'static,
+        B: Into<Behavior<I, O>>,
+    {
+        let (description, sequences) = mock.sequences();
+        self.steps.push(Step {
+            description,
+            sequences,
+        });
+        self
+    }
+
+    /// Panics if the steps were not called in order.
+    /// A call can be followed by calls of other steps between them.
+    #[track_caller]
+    pub fn verify(self) {
+        let mut previous: Option<(&Step, u64)> = None;
+        for step in &self.steps {
+            let next = step
+                .sequences
+                .iter()
+                .copied()
+                .find(|sequence| match previous {
+                    Some((_, previous)) => *sequence > previous,
+                    None => true,
+                });
+            match (next, previous) {
+                (Some(sequence), _) => previous = Some((step, sequence)),
+                (None, Some((previous, _))) if !step.sequences.is_empty() => panic!(
+                    "calls are not in order: {} was not called after {}",
+                    step.description, previous.description
+                ),
+                (None, _) => panic!(
+                    "calls are not in order: {} was not called",
+                    step.description
+                ),
+            }
+        }
+    }
+}
+
+/// Verifies the mocks were called in the order, like `InOrder`.
+#[macro_export]
+macro_rules! sequence {
+    ($($mock:expr),* $(,)?) => {
+        $crate::InOrder::new()$(.then($mock))*.verify()
+    };
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn in_order(steps: Vec<(&str, Vec<u64>)>) -> InOrder {
+        InOrder {
+            steps: steps
+                .into_iter()
+                .map(|(description, sequences)| Step {
+                    description: description.into(),
+                    sequences,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn in_order_with_other_calls_between() {
+        in_order(vec![("a", vec![1, 5]), ("b", vec![0, 3]), ("c", vec![4])]).verify();
+    }
+
+    #[test]
+    #[should_panic(expected = "calls are not in order: c was not called after b")]
+    fn not_in_order() {
+        in_order(vec![("a", vec![1]), ("b", vec![3]), ("c", vec![2])]).verify();
+    }
+
+    #[test]
+    #[should_panic(expected = "calls are not in order: b was not called")]
+    fn not_called() {
+        in_order(vec![("a", vec![1]), ("b", vec![])]).verify();
+    }
+}
@@ -1,14 +1,28 @@
+mod captor;
+mod delay;
+mod fallback;
+mod in_order;
+pub mod matchers;
 mod mock;
 mod mock_locator;
+mod mock_output;
 mod mocks;
 mod mry;
 mod rule;
 mod static_mocks;
+mod strict;
 
 pub use crate::mry::*;
+pub use captor::*;
+pub use fallback::*;
+pub use in_order::*;
+pub use matchers::{pred, pred_named};
+pub use mock::{Call, ServedBy};
 pub use mock_locator::*;
+pub use mock_output::*;
 pub use mocks::*;
-pub use mry_macros::{lock, m, mry, new};
+pub use mry_macros::{lock, m, mry, new, pat, spy};
 pub use rule::*;
 pub use static_mocks::*;
+pub use strict::*;
 pub use Matcher::Any;
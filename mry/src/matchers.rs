@@ -0,0 +1,482 @@
+//! Argument matchers for `mock_*` methods
+
+use std::fmt::Debug;
+use std::ops::{RangeBounds, Sub};
+use std::panic::Location;
+
+use crate::{CompositeMatcher, Matcher};
+
+/// Matcher that delegates to a predicate
+pub struct Predicate<T> {
+    description: String,
+    predicate: Box<dyn Fn(&T) -> bool + Send + Sync + 'static>,
+    comparable: bool,
+}
+
+impl<T> Predicate<T> {
+    /// Creates a predicate matcher printed as `description` in failure messages.
+    pub fn new<F>(description: impl Into<String>, predicate: F) -> Self
+    where
+        F: Fn(&T) -> bool + Send + Sync + 'static,
+    {
+        Self {
+            description: description.into(),
+            predicate: Box::new(predicate),
+            comparable: false,
+        }
+    }
+
+    /// Marks the description as identifying the predicate, so `replace` can compare it.
+    fn comparable(mut self) -> Self {
+        self.comparable = true;
+        self
+    }
+}
+
+impl<T> Debug for Predicate<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.description)
+    }
+}
+
+impl<T> CompositeMatcher<T> for Predicate<T> {
+    fn matches(&self, input: &T) -> bool {
+        (self.predicate)(input)
+    }
+
+    fn is_comparable(&self) -> bool {
+        self.comparable
+    }
+}
+
+impl<T: 'static> From<Predicate<T>> for Matcher<T> {
+    fn from(predicate: Predicate<T>) -> Self {
+        Matcher::Composite(Box::new(predicate))
+    }
+}
+
+/// Matches a value for which the given predicate returns `true`.
+/// The matcher is described by the location where it is created.
+///
+/// ```
+/// let matcher: mry::Matcher<usize> = mry::pred(|count: &usize| *count > 3);
+/// ```
+#[track_caller]
+pub fn pred<T: 'static, F>(predicate: F) -> Matcher<T>
+where
+    F: Fn(&T) -> bool + Send + Sync + 'static,
+{
+    Predicate::new(format!("Pred({})", Location::caller()), predicate).into()
+}
+
+/// Matches a value for which the given predicate returns `true`.
+/// The matcher is described by `description` in failure messages.
+///
+/// ```
+/// let matcher: mry::Matcher<usize> = mry::pred_named("count > 3", |count| *count > 3);
+/// ```
+pub fn pred_named<T: 'static, F>(description: impl Into<String>, predicate: F) -> Matcher<T>
+where
+    F: Fn(&T) -> bool + Send + Sync + 'static,
+{
+    Predicate::new(format!("Pred({})", description.into()), predicate).into()
+}
+
+/// Matches a value equal to the given value. This is the same as passing the value itself.
+pub fn eq<T>(value: T) -> Matcher<T>
+where
+    T: PartialEq + Debug + Send + Sync + 'static,
+{
+    Predicate::new(format!("Eq({:?})", value), move |input| *input == value)
+        .comparable()
+        .into()
+}
+
+/// Matches a value not equal to the given value.
+pub fn ne<T>(value: T) -> Matcher<T>
+where
+    T: PartialEq + Debug + Send + Sync + 'static,
+{
+    Predicate::new(format!("Ne({:?})", value), move |input| *input != value).into()
+}
+
+/// Matches a value greater than the given value.
+pub fn gt<T>(value: T) -> Matcher<T>
+where
+    T: PartialOrd + Debug + Send + Sync + 'static,
+{
+    Predicate::new(format!("Gt({:?})", value), move |input| *input > value).into()
+}
+
+/// Matches a value greater than or equal to the given value.
+pub fn ge<T>(value: T) -> Matcher<T>
+where
+    T: PartialOrd + Debug + Send + Sync + 'static,
+{
+    Predicate::new(format!("Ge({:?})", value), move |input| *input >= value).into()
+}
+
+/// Matches a value less than the given value.
+pub fn lt<T>(value: T) -> Matcher<T>
+where
+    T: PartialOrd + Debug + Send + Sync + 'static,
+{
+    Predicate::new(format!("Lt({:?})", value), move |input| *input < value).into()
+}
+
+/// Matches a value less than or equal to the given value.
+pub fn le<T>(value: T) -> Matcher<T>
+where
+    T: PartialOrd + Debug + Send + Sync + 'static,
+{
+    Predicate::new(format!("Le({:?})", value), move |input| *input <= value).into()
+}
+
+/// Matches a value within the given range such as `1..5` or `..=3`.
+pub fn in_range<T, R>(range: R) -> Matcher<T>
+where
+    T: PartialOrd + 'static,
+    R: RangeBounds<T> + Debug + Send + Sync + 'static,
+{
+    Predicate::new(format!("InRange({:?})", range), move |input| {
+        range.contains(input)
+    })
+    .into()
+}
+
+/// Matches a value within `epsilon` of the given value.
+pub fn approx<T>(value: T, epsilon: T) -> Matcher<T>
+where
+    T: PartialOrd + Sub<Output = T> + Copy + Debug + Send + Sync + 'static,
+{
+    Predicate::new(
+        format!("Approx({:?}, epsilon: {:?})", value, epsilon),
+        move |input| {
+            let difference = if *input > value {
+                *input - value
+            } else {
+                value - *input
+            };
+            difference <= epsilon
+        },
+    )
+    .into()
+}
+
+/// Matches a collection containing the given element.
+pub fn contains<C, E>(element: E) -> Matcher<C>
+where
+    C: 'static,
+    for<'a> &'a C: IntoIterator<Item = &'a E>,
+    E: PartialEq + Debug + Send + Sync + 'static,
+{
+    Predicate::new(format!("Contains({:?})", element), move |input: &C| {
+        input.into_iter().any(|item| *item == element)
+    })
+    .into()
+}
+
+/// Matches a collection with the given number of elements.
+pub fn len<C>(len: usize) -> Matcher<C>
+where
+    C: 'static,
+    for<'a> &'a C: IntoIterator,
+{
+    Predicate::new(format!("Len({})", len), move |input: &C| {
+        input.into_iter().count() == len
+    })
+    .into()
+}
+
+/// Matches a string starting with the given prefix.
+pub fn starts_with(prefix: impl Into<String>) -> Matcher<String> {
+    let prefix = prefix.into();
+    Predicate::new(
+        format!("StartsWith({:?})", prefix),
+        move |input: &String| input.starts_with(&prefix),
+    )
+    .into()
+}
+
+/// Matches a string ending with the given suffix.
+pub fn ends_with(suffix: impl Into<String>) -> Matcher<String> {
+    let suffix = suffix.into();
+    Predicate::new(format!("EndsWith({:?})", suffix), move |input: &String| {
+        input.ends_with(&suffix)
+    })
+    .into()
+}
+
+/// Matches a string containing the given substring.
+pub fn contains_str(substring: impl Into<String>) -> Matcher<String> {
+    let substring = substring.into();
+    Predicate::new(
+        format!("ContainsStr({:?})", substring),
+        move |input: &String| input.contains(&substring),
+    )
+    .into()
+}
+
+/// Matcher that matches a value matched by all of the matchers
+pub struct AllOf<T>(Vec<Matcher<T>>);
+
+impl<T: Debug> Debug for AllOf<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut tuple = f.debug_tuple("AllOf");
+        self.0.iter().for_each(|matcher| {
+            tuple.field(matcher);
+        });
+        tuple.finish()
+    }
+}
+
+impl<T: Debug> CompositeMatcher<T> for AllOf<T> {
+    fn matches(&self, input: &T) -> bool {
+        self.0.iter().all(|matcher| matcher.matches(input))
+    }
+
+    fn capture(&self, input: &T) {
+        self.0.iter().for_each(|matcher| matcher.capture(input));
+    }
+}
+
+/// Matcher that matches a value matched by any of the matchers
+pub struct AnyOf<T>(Vec<Matcher<T>>);
+
+impl<T: Debug> Debug for AnyOf<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut tuple = f.debug_tuple("AnyOf");
+        self.0.iter().for_each(|matcher| {
+            tuple.field(matcher);
+        });
+        tuple.finish()
+    }
+}
+
+impl<T: Debug> CompositeMatcher<T> for AnyOf<T> {
+    fn matches(&self, input: &T) -> bool {
+        self.0.iter().any(|matcher| matcher.matches(input))
+    }
+
+    fn capture(&self, input: &T) {
+        self.0
+            .iter()
+            .filter(|matcher| matcher.matches(input))
+            .for_each(|matcher| matcher.capture(input));
+    }
+}
+
+/// Matcher that matches a value not matched by the matcher
+pub struct Not<T>(Matcher<T>);
+
+impl<T: Debug> Debug for Not<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Not").field(&self.0).finish()
+    }
+}
+
+impl<T: Debug> CompositeMatcher<T> for Not<T> {
+    fn matches(&self, input: &T) -> bool {
+        !self.0.matches(input)
+    }
+}
+
+/// Matches a value matched by all of the matchers. See also `mry::all_of!`.
+pub fn all_of<T>(matchers: Vec<Matcher<T>>) -> Matcher<T>
+where
+    T: Debug + Send + Sync + 'static,
+{
+    Matcher::Composite(Box::new(AllOf(matchers)))
+}
+
+/// Matches a value matched by any of the matchers. See also `mry::any_of!`.
+pub fn any_of<T>(matchers: Vec<Matcher<T>>) -> Matcher<T>
+where
+    T: Debug + Send + Sync + 'static,
+{
+    Matcher::Composite(Box::new(AnyOf(matchers)))
+}
+
+/// Matches a value not matched by the matcher.
+pub fn not<T>(matcher: impl Into<Matcher<T>>) -> Matcher<T>
+where
+    T: Debug + Send + Sync + 'static,
+{
+    Matcher::Composite(Box::new(Not(matcher.into())))
+}
+
+/// Matches a value matched by all of the given matchers or values.
+///
+/// ```
+/// let matcher: mry::Matcher<usize> = mry::all_of![mry::matchers::gt(1), mry::matchers::lt(5)];
+/// ```
+#[macro_export]
+macro_rules! all_of {
+    ($($matcher:expr),* $(,)?) => {
+        $crate::matchers::all_of(vec![$($crate::Matcher::from($matcher)),*])
+    };
+}
+
+/// Matches a value matched by any of the given matchers or values.
+///
+/// ```
+/// let matcher: mry::Matcher<usize> = mry::any_of![1, 2, mry::matchers::gt(10)];
+/// ```
+#[macro_export]
+macro_rules! any_of {
+    ($($matcher:expr),* $(,)?) => {
+        $crate::matchers::any_of(vec![$($crate::Matcher::from($matcher)),*])
+    };
+}
+
+/// Matches `Some(_)`.
+pub fn is_some<T: 'static>() -> Matcher<Option<T>> {
+    Predicate::new("IsSome", Option::is_some).into()
+}
+
+/// Matches `None`.
+pub fn is_none<T: 'static>() -> Matcher<Option<T>> {
+    Predicate::new("IsNone", Option::is_none).into()
+}
+
+/// Matches `Ok(_)`.
+pub fn is_ok<T: 'static, E: 'static>() -> Matcher<Result<T, E>> {
+    Predicate::new("IsOk", Result::is_ok).into()
+}
+
+/// Matches `Err(_)`.
+pub fn is_err<T: 'static, E: 'static>() -> Matcher<Result<T, E>> {
+    Predicate::new("IsErr", Result::is_err).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pred_matches() {
+        let matcher = pred(|count: &usize| *count > 3);
+        assert!(matcher.matches(&4));
+        assert!(!matcher.matches(&3));
+    }
+
+    #[test]
+    fn pred_debug_shows_location() {
+        let matcher = pred(|count: &usize| *count > 3);
+        assert!(format!("{:?}", matcher).contains(file!()));
+    }
+
+    #[test]
+    fn pred_named_debug_shows_description() {
+        let matcher = pred_named("count > 3", |count: &usize| *count > 3);
+        assert_eq!(format!("{:?}", matcher), "Pred(count > 3)");
+    }
+
+    #[test]
+    fn comparisons() {
+        assert!(ne(3).matches(&2));
+        assert!(!ne(3).matches(&3));
+        assert!(gt(3).matches(&4));
+        assert!(!gt(3).matches(&3));
+        assert!(ge(3).matches(&3));
+        assert!(!ge(3).matches(&2));
+        assert!(lt(3).matches(&2));
+        assert!(!lt(3).matches(&3));
+        assert!(le(3).matches(&3));
+        assert!(!le(3).matches(&4));
+    }
+
+    #[test]
+    fn ranges() {
+        assert!(in_range(1..3).matches(&1));
+        assert!(!in_range(1..3).matches(&3));
+        assert!(in_range(..=3).matches(&3));
+        assert!(approx(1.0, 0.01).matches(&1.005));
+        assert!(approx(1.0, 0.01).matches(&0.995));
+        assert!(!approx(1.0, 0.01).matches(&1.02));
+    }
+
+    #[test]
+    fn collections() {
+        assert!(contains(2).matches(&vec![1, 2, 3]));
+        assert!(!contains(4).matches(&vec![1, 2, 3]));
+        assert!(len(3).matches(&vec![1, 2, 3]));
+        assert!(!len(2).matches(&vec![1, 2, 3]));
+    }
+
+    #[test]
+    fn strings() {
+        assert!(starts_with("me").matches(&"meow".to_string()));
+        assert!(!starts_with("ow").matches(&"meow".to_string()));
+        assert!(ends_with("ow").matches(&"meow".to_string()));
+        assert!(!ends_with("me").matches(&"meow".to_string()));
+        assert!(contains_str("eo").matches(&"meow".to_string()));
+        assert!(!contains_str("oe").matches(&"meow".to_string()));
+    }
+
+    #[test]
+    fn options_and_results() {
+        assert!(is_some().matches(&Some(1)));
+        assert!(!is_some().matches(&None::<u8>));
+        assert!(is_none().matches(&None::<u8>));
+        assert!(is_ok().matches(&Ok::<u8, u8>(1)));
+        assert!(!is_ok().matches(&Err::<u8, u8>(1)));
+        assert!(is_err().matches(&Err::<u8, u8>(1)));
+    }
+
+    #[test]
+    fn debug_descriptions() {
+        assert_eq!(format!("{:?}", gt(3)), "Gt(3)");
+        assert_eq!(format!("{:?}", in_range(1..3)), "InRange(1..3)");
+        assert_eq!(
+            format!("{:?}", approx(1.0, 0.5)),
+            "Approx(1.0, epsilon: 0.5)"
+        );
+        assert_eq!(format!("{:?}", contains::<Vec<u8>, u8>(2)), "Contains(2)");
+        assert_eq!(format!("{:?}", starts_with("me")), "StartsWith(\"me\")");
+        assert_eq!(format!("{:?}", is_none::<u8>()), "IsNone");
+    }
+
+    #[test]
+    fn combinators() {
+        let matcher: Matcher<u8> = crate::all_of![gt(1), lt(5), not(3)];
+        assert!(matcher.matches(&2));
+        assert!(!matcher.matches(&3));
+        assert!(!matcher.matches(&5));
+
+        let matcher: Matcher<u8> = crate::any_of![1, 2, gt(10)];
+        assert!(matcher.matches(&1));
+        assert!(matcher.matches(&11));
+        assert!(!matcher.matches(&3));
+    }
+
+    #[test]
+    fn combinators_nested() {
+        let matcher: Matcher<u8> = crate::any_of![crate::all_of![gt(1), lt(3)], not(lt(10))];
+        assert!(matcher.matches(&2));
+        assert!(matcher.matches(&10));
+        assert!(!matcher.matches(&3));
+        assert!(!matcher.matches(&0));
+    }
+
+    #[test]
+    fn debug_combinators() {
+        let matcher: Matcher<u8> = crate::any_of![crate::all_of![gt(1), lt(3)], not(0)];
+        assert_eq!(
+            format!("{:?}", matcher),
+            "AnyOf(AllOf(Gt(1), Lt(3)), Not(Eq(0)))"
+        );
+    }
+
+    #[test]
+    fn pred_as_argument_matcher() {
+        let matcher: Matcher<(usize, String)> = (
+            pred(|count: &usize| *count > 3),
+            pred(|path: &String| path.ends_with(".json")),
+        )
+            .into();
+        assert!(matcher.matches(&(4, "a.json".to_string())));
+        assert!(!matcher.matches(&(4, "a.toml".to_string())));
+        assert!(!matcher.matches(&(3, "a.json".to_string())));
+    }
+}
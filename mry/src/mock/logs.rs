@@ -1,22 +1,127 @@
+use std::fmt::Debug;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::thread::ThreadId;
+use std::time::SystemTime;
+
+use once_cell::sync::OnceCell;
+
 use crate::Matcher;
 
-#[derive(Debug, PartialEq)]
-pub struct Logs<I>(pub Vec<I>);
+/// Global sequence of calls, which is monotonic across mocks
+static SEQUENCE: AtomicU64 = AtomicU64::new(0);
+
+/// The output of a call rendered by `Debug`, which is set when the output is produced
+pub(crate) type RecordedOutput = Arc<OnceCell<String>>;
+
+/// What produced the output of a call
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ServedBy {
+    /// A rule returned the output
+    Rule,
+    /// A rule called the real implementation
+    RealImpl,
+    /// No rule matched, and the fallback was used
+    Fallback,
+}
+
+/// A recorded call
+#[derive(Clone, PartialEq)]
+pub struct Call<I> {
+    /// The arguments before the call
+    pub input: I,
+    /// The order of the call among all recorded calls
+    pub sequence: u64,
+    pub served_by: ServedBy,
+    pub thread: ThreadId,
+    pub timestamp: SystemTime,
+    output: RecordedOutput,
+}
+
+impl<I> Call<I> {
+    /// The output rendered by `Debug`.
+    /// This is `None` until the output is produced, or if the real implementation of a method taking references is called.
+    pub fn output(&self) -> Option<&str> {
+        self.output.get().map(String::as_str)
+    }
+
+    /// Converts the input into a call record type.
+    pub(crate) fn map_input<C: From<I>>(self) -> Call<C> {
+        Call {
+            input: self.input.into(),
+            sequence: self.sequence,
+            served_by: self.served_by,
+            thread: self.thread,
+            timestamp: self.timestamp,
+            output: self.output,
+        }
+    }
+}
+
+impl<I: Debug> Debug for Call<I> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Call")
+            .field("input", &self.input)
+            .field("output", &self.output())
+            .field("served_by", &self.served_by)
+            .field("sequence", &self.sequence)
+            .field("thread", &self.thread)
+            .field("timestamp", &self.timestamp)
+            .finish()
+    }
+}
+
+#[derive(PartialEq)]
+pub struct Logs<I>(pub Vec<Call<I>>);
+
+impl<I: Clone> Logs<I> {
+    /// Records a call served by a rule, and returns the slot of the output.
+    pub(crate) fn push(&mut self, input: I) -> RecordedOutput {
+        let output = RecordedOutput::default();
+        self.0.push(Call {
+            input,
+            sequence: SEQUENCE.fetch_add(1, Ordering::SeqCst),
+            served_by: ServedBy::Rule,
+            thread: std::thread::current().id(),
+            timestamp: SystemTime::now(),
+            output: output.clone(),
+        });
+        output
+    }
+
+    /// Sets what served the last call.
+    pub(crate) fn served_by(&mut self, served_by: ServedBy) {
+        if let Some(call) = self.0.last_mut() {
+            call.served_by = served_by;
+        }
+    }
 
-impl<I: PartialEq + Clone> Logs<I> {
-    pub(crate) fn push(&mut self, item: I) {
-        self.0.push(item);
+    /// Marks the last call as served by the real implementation, if a rule served it.
+    pub(crate) fn real_impl_called(&mut self) {
+        if let Some(call) = self.0.last_mut() {
+            if call.served_by == ServedBy::Rule {
+                call.served_by = ServedBy::RealImpl;
+            }
+        }
     }
 
     pub(crate) fn filter_matches(&self, matcher: &Matcher<I>) -> Self {
         Self(
             self.0
                 .iter()
-                .filter(|log| matcher.matches(log))
+                .filter(|call| matcher.matches(&call.input))
                 .cloned()
                 .collect(),
         )
     }
+
+    pub(crate) fn inputs(self) -> Vec<I> {
+        self.0.into_iter().map(|call| call.input).collect()
+    }
+
+    pub(crate) fn sequences(&self) -> Vec<u64> {
+        self.0.iter().map(|call| call.sequence).collect()
+    }
 }
 
 impl<I> Default for Logs<I> {
@@ -25,13 +130,55 @@ impl<I> Default for Logs<I> {
     }
 }
 
+/// Shows only the inputs, since sequences differ between runs
+impl<I: Debug> Debug for Logs<I> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Logs")
+            .field(&self.0.iter().map(|call| &call.input).collect::<Vec<_>>())
+            .finish()
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
 
+    fn logs(inputs: Vec<u8>) -> Logs<u8> {
+        let mut logs = Logs::default();
+        for input in inputs {
+            logs.push(input);
+        }
+        logs
+    }
+
     #[test]
     fn filter_matches() {
-        let logs = Logs(vec![1, 2, 2, 3, 4, 2]);
-        assert_eq!(logs.filter_matches(&Matcher::Eq(2)), Logs(vec![2, 2, 2]));
+        let logs = logs(vec![1, 2, 2, 3, 4, 2]);
+        assert_eq!(logs.filter_matches(&Matcher::eq(2)).inputs(), vec![2, 2, 2]);
+    }
+
+    #[test]
+    fn sequences_are_monotonic() {
+        let sequences = logs(vec![1, 2, 3]).sequences();
+        assert!(sequences.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn debug_shows_inputs() {
+        assert_eq!(format!("{:?}", logs(vec![1, 2])), "Logs([1, 2])");
+    }
+
+    #[test]
+    fn records_metadata() {
+        let mut logs = Logs::default();
+        let output = logs.push(1u8);
+        logs.served_by(ServedBy::Fallback);
+        output.set("2".into()).unwrap();
+
+        let call = &logs.0[0];
+        assert_eq!(call.served_by, ServedBy::Fallback);
+        assert_eq!(call.output(), Some("2"));
+        assert_eq!(call.thread, std::thread::current().id());
+        assert!(call.timestamp <= SystemTime::now());
     }
 }
@@ -1,79 +1,370 @@
 mod logs;
-use std::fmt::Debug;
-use std::iter::repeat;
+mod report;
+use std::fmt::{Debug, Write};
+use std::future::ready;
+use std::iter::{once, repeat};
+use std::panic::Location;
 
 pub use logs::*;
+use report::{assertion_failure, mock_not_found, sequence_exhausted, shadowed};
 
-use parking_lot::{Mutex, RwLock};
+use parking_lot::Mutex;
 
-use crate::{times::Times, Behavior, Matcher, Output, Rule};
+use crate::fallback::current_default_fallback;
+use crate::{
+    current_default_rule_order, times::Times, Behavior, BoxFuture, Fallback, Matcher, MockOutput,
+    Output, Rule, RuleOrder,
+};
 
 pub struct Mock<I, O> {
     pub name: &'static str,
+    /// Names of the parameters, used in failure messages instead of positions if known
+    pub(crate) params: &'static [&'static str],
     logs: Mutex<Logs<I>>,
     rules: Vec<Rule<I, O>>,
+    fallback: Option<MethodFallback<I, O>>,
+    rule_order: Option<RuleOrder>,
+    /// The fallback of the object, used if the method doesn't have one.
+    pub(crate) object_fallback: Option<Fallback>,
+    /// `Default::default` of the output type if it implements `Default`, used by `Fallback::Nice`.
+    pub(crate) default: Option<fn() -> O>,
+    /// Where the method is called this time, used in failure messages if known
+    pub(crate) called_at: Option<&'static Location<'static>>,
+}
+
+/// Fallback of a method, which can be a behavior unlike the fallback of an object
+pub(crate) enum MethodFallback<I, O> {
+    Fallback(Fallback),
+    Behavior(Behavior<I, O>),
 }
 
 impl<I, O> Mock<I, O> {
     pub fn new(name: &'static str) -> Self {
         Self {
             name,
+            params: &[],
             logs: Default::default(),
             rules: Default::default(),
+            fallback: None,
+            rule_order: None,
+            object_fallback: None,
+            default: None,
+            called_at: None,
         }
     }
+
+    /// Removes the rules, logs, and settings of the method.
+    /// The mock itself is kept, so a lock of a static function is still regarded as used.
+    pub(crate) fn reset(&mut self) {
+        self.rules.clear();
+        self.fallback = None;
+        self.rule_order = None;
+        self.clear_logs();
+    }
+
+    pub(crate) fn clear_logs(&mut self) {
+        self.logs.get_mut().0.clear();
+    }
 }
 
-impl<I: Clone + PartialEq + Debug, O: Debug> Mock<I, O> {
-    pub(crate) fn returns_with(&mut self, matcher: Matcher<I>, behavior: Behavior<I, O>) {
-        self.rules.push(Rule {
-            matcher,
-            behavior: behavior,
-        });
+impl<I: Debug, O> Mock<I, O> {
+    /// Describes the rules which haven't matched the expected times.
+    pub(crate) fn unmet_expectations(&self) -> Vec<String> {
+        self.rules
+            .iter()
+            .enumerate()
+            .filter(|(_, rule)| !rule.is_expectation_met())
+            .map(|(index, rule)| {
+                let message = format!(
+                    "{} was expected to be called {} times with {:?}, but called {} times",
+                    self.name,
+                    rule.expected.unwrap(),
+                    rule.matcher,
+                    rule.hits
+                );
+                self.describe_unmatched(index, message)
+            })
+            .collect()
+    }
+
+    /// Describes the rules which have never matched, if they are strict or the object is strict.
+    pub(crate) fn unused_rules(&self, strict: bool) -> Vec<String> {
+        self.rules
+            .iter()
+            .enumerate()
+            .filter(|(_, rule)| rule.is_unused(strict))
+            .map(|(index, rule)| {
+                let message = format!("{} with {:?} never matched", self.name, rule.matcher);
+                self.describe_unmatched(index, message)
+            })
+            .collect()
+    }
+
+    /// Adds where the rule is added and the earlier rule shadowing it to the message.
+    fn describe_unmatched(&self, index: usize, mut message: String) -> String {
+        let rule = &self.rules[index];
+        if let Some(location) = rule.location {
+            write!(message, ", added at {}", location).unwrap();
+        }
+        if let Some(shadowing) = self.shadowing_rule(index) {
+            message.push_str(&shadowed(shadowing));
+        }
+        message
+    }
+
+    /// The earlier rule which matches any input forever, so the rule at the index never matches.
+    fn shadowing_rule(&self, index: usize) -> Option<&Rule<I, O>> {
+        if self.rule_order() != RuleOrder::FirstWins {
+            return None;
+        }
+        self.rules[..index]
+            .iter()
+            .find(|rule| rule.shadows_later_rules())
+    }
+
+    fn rule_order(&self) -> RuleOrder {
+        self.rule_order
+            .or_else(current_default_rule_order)
+            .unwrap_or(RuleOrder::FirstWins)
+    }
+}
+
+impl<I: Clone + Debug, O: Debug> Mock<I, O> {
+    pub(crate) fn returns_with(
+        &mut self,
+        matcher: Matcher<I>,
+        behavior: Behavior<I, O>,
+    ) -> &mut Rule<I, O> {
+        self.rules.push(Rule::new(matcher, behavior));
+        self.rules.last_mut().unwrap()
+    }
+
+    /// Removes the rules with a matcher equivalent to the given one.
+    pub(crate) fn replace(&mut self, matcher: &Matcher<I>) {
+        self.rules
+            .retain(|rule| !rule.matcher.is_equivalent(matcher));
+    }
+
+    pub(crate) fn set_rule_order(&mut self, order: RuleOrder) {
+        self.rule_order = Some(order);
+    }
+
+    pub(crate) fn calls_real_impl(&mut self, matcher: Matcher<I>) -> &mut Rule<I, O> {
+        self.returns_with(matcher, Behavior::CallsRealImpl)
+    }
+
+    #[track_caller]
+    pub(crate) fn assert_consumed(&self) {
+        if let Some(rule) = self
+            .rules
+            .iter()
+            .find(|rule| matches!(rule.limit, Some(limit) if rule.hits < limit))
+        {
+            panic!("{} has a rule not fully consumed\n{:?}", self.name, rule)
+        }
     }
 
-    pub(crate) fn calls_real_impl(&mut self, matcher: Matcher<I>) {
-        self.rules.push(Rule {
-            matcher,
-            behavior: Behavior::CallsRealImpl,
-        })
+    pub(crate) fn calls(&self, matcher: &Matcher<I>) -> Vec<Call<I>> {
+        self.logs.lock().filter_matches(matcher).0
     }
 
+    /// Returns the sequences of the calls matching the matcher, which are used to verify the order of calls.
+    pub(crate) fn sequences(&self, matcher: &Matcher<I>) -> Vec<u64> {
+        self.logs.lock().filter_matches(matcher).sequences()
+    }
+
+    #[cfg(test)]
     pub(crate) fn assert_called(&self, matcher: Matcher<I>, times: Times) -> Logs<I> {
+        self.assert_called_as::<I>(matcher, times, self.params)
+    }
+
+    /// Calls are shown as the call record `C` with the arguments named by `params` in failure messages.
+    #[track_caller]
+    pub(crate) fn assert_called_as<C: From<I> + Debug>(
+        &self,
+        matcher: Matcher<I>,
+        times: Times,
+        params: &[&str],
+    ) -> Logs<I> {
         let logs = self.logs.lock().filter_matches(&matcher);
-        if !times.contains(&dbg!(logs.0.len())) {
-            panic!("{} was not called\n{:?}", self.name, *self.logs.lock())
+        if !times.contains(&logs.0.len()) {
+            panic!(
+                "{}",
+                assertion_failure(
+                    self.name,
+                    Location::caller(),
+                    params,
+                    &matcher,
+                    times,
+                    logs.0.len(),
+                    &self.logs.lock().0,
+                    &|input: &I| format!("{:?}", C::from(input.clone())),
+                    &self.rules
+                )
+            )
         }
         logs
     }
 
-    pub(crate) fn record_call_and_find_mock_output(&mut self, input: I) -> Option<O> {
-        self.logs.lock().push(input.clone());
-        for rule in &mut self.rules {
-            match rule.called(&input) {
-                Output::Found(output) => return Some(output),
+    pub(crate) fn returns_iter<T>(&mut self, matcher: Matcher<I>, iter: T) -> &mut Rule<I, O>
+    where
+        T: Iterator<Item = O> + Send + Sync + 'static,
+    {
+        self.returns_with(matcher, Behavior::values(iter))
+    }
+
+    pub(crate) fn fallback(&mut self, fallback: MethodFallback<I, O>) {
+        self.fallback = Some(fallback);
+    }
+
+    pub(crate) fn record_call_and_find_mock_output(&mut self, mut input: I) -> Option<O> {
+        self.record_call_and_find_mock_output_mut(&mut input)
+    }
+
+    pub(crate) fn record_call_and_find_mock_future(&mut self, mut input: I) -> Option<BoxFuture<O>>
+    where
+        O: Send + 'static,
+    {
+        self.record_call_and_find_mock_future_mut(&mut input)
+    }
+
+    /// Behaviors can modify the input to write it back to `&mut` arguments.
+    pub(crate) fn record_call_and_find_mock_output_mut(&mut self, input: &mut I) -> Option<O> {
+        match self.record_call_and_find_output(input).0 {
+            Output::Found(output) => Some(output),
+            Output::Pending(_) => panic!(
+                "{} returns a future, but it is not an async method",
+                self.name
+            ),
+            Output::CallsRealImplWith(_) | Output::WrapsRealImpl(_) => {
+                self.real_impl_not_supported()
+            }
+            _ => None,
+        }
+    }
+
+    /// The output is resolved with the real implementation by `MockOutput`.
+    pub(crate) fn record_call_and_find_real_impl(&mut self, mut input: I) -> MockOutput<I, O> {
+        let (output, recorded) = self.record_call_and_find_output(&mut input);
+        MockOutput::new(self.name, output).recording(recorded)
+    }
+
+    fn real_impl_not_supported(&self) -> ! {
+        panic!(
+            "calls_real_impl_with and wraps_real_impl are not supported for {} because it takes references",
+            self.name
+        )
+    }
+
+    pub(crate) fn record_call_and_find_mock_future_mut(
+        &mut self,
+        input: &mut I,
+    ) -> Option<BoxFuture<O>>
+    where
+        O: Send + 'static,
+    {
+        let (output, recorded) = self.record_call_and_find_output(input);
+        match output {
+            Output::Found(output) => Some(Box::pin(ready(output))),
+            Output::Pending(future) => Some(Box::pin(async move {
+                let output = future.await;
+                let _ = recorded.set(format!("{:?}", output));
+                output
+            })),
+            Output::CallsRealImplWith(_) | Output::WrapsRealImpl(_) => {
+                self.real_impl_not_supported()
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns an output other than `NotMatches` and `Exhausted`, or uses the fallback if no rule is found.
+    /// The returned slot is where the output is recorded if it is produced later.
+    fn record_call_and_find_output(&mut self, input: &mut I) -> (Output<I, O>, RecordedOutput) {
+        let recorded = self.logs.get_mut().push(input.clone());
+        let output = self.find_output(input);
+        match &output {
+            Output::Found(output) => {
+                let _ = recorded.set(format!("{:?}", output));
+            }
+            Output::CallsRealImpl | Output::CallsRealImplWith(_) | Output::WrapsRealImpl(_) => {
+                self.logs.get_mut().real_impl_called()
+            }
+            _ => {}
+        }
+        (output, recorded)
+    }
+
+    fn find_output(&mut self, input: &mut I) -> Output<I, O> {
+        let mut exhausted = false;
+        let rules: Box<dyn Iterator<Item = &mut Rule<I, O>>> = match self.rule_order() {
+            RuleOrder::FirstWins => Box::new(self.rules.iter_mut()),
+            RuleOrder::LastWins => Box::new(self.rules.iter_mut().rev()),
+        };
+        for rule in rules {
+            match rule.called(input) {
                 Output::NotMatches => {}
-                Output::CallsRealImpl => return None,
+                // Falls through to the next rule
+                Output::Exhausted => exhausted = true,
+                output => return output,
             };
         }
-        panic!("mock not found for {}\n{:?}", self.name, self.rules)
+        self.logs.get_mut().served_by(ServedBy::Fallback);
+        let fallback = match &mut self.fallback {
+            Some(MethodFallback::Behavior(behavior)) => return behavior.called(input),
+            Some(MethodFallback::Fallback(fallback)) => Some(*fallback),
+            None => self.object_fallback.or_else(current_default_fallback),
+        };
+        match fallback.unwrap_or(Fallback::Strict) {
+            Fallback::Strict if exhausted => {
+                panic!(
+                    "{}",
+                    sequence_exhausted(self.name, input, &self.rules, self.called_at)
+                )
+            }
+            Fallback::Strict => panic!(
+                "{}",
+                mock_not_found(self.name, self.params, input, &self.rules, self.called_at)
+            ),
+            Fallback::Nice => match self.default {
+                Some(default) => Output::Found(default()),
+                None => panic!(
+                    "mock not found for {}, and the output type does not implement Default",
+                    self.name
+                ),
+            },
+            // Recorded as served by the fallback
+            Fallback::RealImpl => Output::CallsRealImpl,
+        }
     }
 }
 
 impl<I, O> Mock<I, O>
 where
-    I: Clone + PartialEq + Debug + Send + Sync + 'static,
+    I: Clone + Debug + Send + Sync + 'static,
+    O: Debug + Send + Sync + 'static,
+{
+    pub(crate) fn returns_once(&mut self, matcher: Matcher<I>, ret: O) -> &mut Rule<I, O> {
+        self.returns_iter(matcher, once(ret))
+    }
+}
+
+impl<I, O> Mock<I, O>
+where
+    I: Clone + Debug + Send + Sync + 'static,
     O: Clone + Debug + Send + Sync + 'static,
 {
-    pub(crate) fn returns(&mut self, matcher: Matcher<I>, ret: O) {
-        self.returns_with(matcher, Behavior::Const(RwLock::new(Box::new(repeat(ret)))))
+    pub(crate) fn returns(&mut self, matcher: Matcher<I>, ret: O) -> &mut Rule<I, O> {
+        let rule = self.returns_iter(matcher, repeat(ret));
+        rule.repeats = true;
+        rule
     }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::Behavior1;
+    use crate::{Behavior1, RuleOptions};
 
     #[test]
     fn returns_with() {
@@ -123,6 +414,56 @@ mod test {
         );
     }
 
+    #[test]
+    fn spy_calls_real_impl_when_not_found() {
+        let mut mock = Mock::<usize, String>::new("a");
+        mock.object_fallback = Some(Fallback::RealImpl);
+        mock.returns(Matcher::eq(1), "a".into());
+
+        assert_eq!(mock.record_call_and_find_mock_output(1), Some("a".into()));
+        assert_eq!(mock.record_call_and_find_mock_output(2), None);
+        mock.assert_called(Matcher::Any, 2.into());
+    }
+
+    #[test]
+    fn last_wins() {
+        let mut mock = Mock::<usize, String>::new("a");
+        mock.set_rule_order(RuleOrder::LastWins);
+        mock.returns(Matcher::Any, "first".into());
+        mock.returns(Matcher::Any, "second".into());
+
+        assert_eq!(
+            mock.record_call_and_find_mock_output(3),
+            Some("second".into())
+        );
+    }
+
+    #[test]
+    fn replace_removes_equivalent_rules() {
+        let mut mock = Mock::<usize, String>::new("a");
+        mock.returns(Matcher::eq(1), "one".into());
+        mock.returns(Matcher::eq(2), "two".into());
+        mock.replace(&Matcher::eq(1));
+        mock.returns(Matcher::eq(1), "replaced".into());
+
+        assert_eq!(
+            mock.record_call_and_find_mock_output(1),
+            Some("replaced".into())
+        );
+        assert_eq!(mock.record_call_and_find_mock_output(2), Some("two".into()));
+    }
+
+    #[test]
+    fn replace_keeps_rules_of_captors_and_predicates() {
+        let mut mock = Mock::<usize, String>::new("a");
+        mock.returns(crate::Captor::new().matcher(), "captor".into());
+        mock.returns(crate::pred_named("small", |n| *n < 3), "small".into());
+        mock.replace(&crate::Captor::new().matcher());
+        mock.replace(&crate::pred_named("small", |n| *n < 5));
+
+        assert_eq!(mock.rules.len(), 2);
+    }
+
     #[test]
     #[should_panic(expected = "mock not found for a")]
     fn returns_never() {
@@ -143,10 +484,188 @@ mod test {
         );
     }
 
+    #[test]
+    fn returns_iter() {
+        let mut mock = Mock::<usize, String>::new("a");
+        mock.returns_iter(
+            Matcher::Any,
+            vec!["a".to_string(), "b".to_string()].into_iter(),
+        );
+
+        assert_eq!(
+            mock.record_call_and_find_mock_output(3),
+            Some("a".to_string())
+        );
+        assert_eq!(
+            mock.record_call_and_find_mock_output(3),
+            Some("b".to_string())
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "sequence exhausted for a")]
+    fn returns_once_exhausted() {
+        let mut mock = Mock::<usize, String>::new("a");
+        mock.returns_once(Matcher::Any, "a".to_string());
+
+        mock.record_call_and_find_mock_output(3);
+        mock.record_call_and_find_mock_output(3);
+    }
+
+    #[test]
+    fn returns_once_falls_through() {
+        let mut mock = Mock::<usize, String>::new("a");
+        mock.returns_once(Matcher::Any, "a".to_string());
+        mock.returns(Matcher::Any, "b".to_string());
+
+        assert_eq!(
+            mock.record_call_and_find_mock_output(3),
+            Some("a".to_string())
+        );
+        assert_eq!(
+            mock.record_call_and_find_mock_output(3),
+            Some("b".to_string())
+        );
+        assert_eq!(
+            mock.record_call_and_find_mock_output(3),
+            Some("b".to_string())
+        );
+    }
+
+    #[test]
+    fn limited_rule_falls_through() {
+        let mut mock = Mock::<usize, String>::new("a");
+        mock.returns(Matcher::Any, "a".to_string())
+            .apply(RuleOptions {
+                limit: Some(2),
+                ..Default::default()
+            });
+        mock.returns(Matcher::Any, "b".to_string());
+
+        assert_eq!(
+            mock.record_call_and_find_mock_output(3),
+            Some("a".to_string())
+        );
+        assert_eq!(
+            mock.record_call_and_find_mock_output(3),
+            Some("a".to_string())
+        );
+        assert_eq!(
+            mock.record_call_and_find_mock_output(3),
+            Some("b".to_string())
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "mock not found for a")]
+    fn limited_rule_retired() {
+        let mut mock = Mock::<usize, String>::new("a");
+        mock.returns(Matcher::Any, "a".to_string())
+            .apply(RuleOptions {
+                limit: Some(1),
+                ..Default::default()
+            });
+
+        mock.record_call_and_find_mock_output(3);
+        mock.record_call_and_find_mock_output(3);
+    }
+
+    #[test]
+    fn assert_consumed() {
+        let mut mock = Mock::<usize, String>::new("a");
+        mock.returns(Matcher::Any, "a".to_string())
+            .apply(RuleOptions {
+                limit: Some(1),
+                ..Default::default()
+            });
+        mock.returns(Matcher::Any, "b".to_string());
+
+        mock.record_call_and_find_mock_output(3);
+
+        mock.assert_consumed();
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "a has a rule not fully consumed\nRule { matcher: Any, behavior: Const(\"a\"), limit: 2, hits: 1 }"
+    )]
+    fn assert_consumed_panics() {
+        let mut mock = Mock::<usize, String>::new("a");
+        mock.returns(Matcher::Any, "a".to_string())
+            .apply(RuleOptions {
+                limit: Some(2),
+                ..Default::default()
+            });
+
+        mock.record_call_and_find_mock_output(3);
+
+        mock.assert_consumed();
+    }
+
+    #[test]
+    fn returns_future() {
+        let mut mock = Mock::<usize, String>::new("a");
+        mock.returns_with(
+            Matcher::Any,
+            Behavior::Async(Box::new(|a| Box::pin(async move { "a".repeat(a) }))),
+        );
+        mock.returns(Matcher::Any, "b".to_string());
+
+        let future = mock.record_call_and_find_mock_future(3).unwrap();
+
+        assert_eq!(async_std::task::block_on(future), "aaa".to_string());
+    }
+
+    #[test]
+    fn returns_future_of_const() {
+        let mut mock = Mock::<usize, String>::new("a");
+        mock.returns(Matcher::Any, "a".to_string());
+
+        let future = mock.record_call_and_find_mock_future(3).unwrap();
+
+        assert_eq!(async_std::task::block_on(future), "a".to_string());
+    }
+
+    #[test]
+    #[should_panic(expected = "a returns a future, but it is not an async method")]
+    fn returns_future_to_sync_method() {
+        let mut mock = Mock::<usize, String>::new("a");
+        mock.returns_with(
+            Matcher::Any,
+            Behavior::Async(Box::new(|a| Box::pin(async move { "a".repeat(a) }))),
+        );
+
+        mock.record_call_and_find_mock_output(3);
+    }
+
+    #[test]
+    fn returns_with_mut_writes_input() {
+        let mut mock = Mock::<Vec<u8>, usize>::new("a");
+        mock.returns_with(
+            Matcher::Any,
+            Behavior::FunctionMut(Box::new(|buf: &mut Vec<u8>| {
+                buf.push(1);
+                buf.len()
+            })),
+        );
+        let mut input = vec![0];
+
+        assert_eq!(
+            mock.record_call_and_find_mock_output_mut(&mut input),
+            Some(2)
+        );
+        assert_eq!(input, vec![0, 1]);
+        // logs the snapshot before the call
+        assert_eq!(
+            mock.assert_called(Matcher::Any, Times::Exact(1)).inputs(),
+            vec![vec![0]]
+        );
+    }
+
     #[test]
     fn calls_real_impl() {
         let mut mock = Mock::<usize, String>::new("a");
-        mock.calls_real_impl(Matcher::Eq(3));
+        mock.calls_real_impl(Matcher::eq(3));
 
         assert_eq!(mock.record_call_and_find_mock_output(3), None);
     }
@@ -155,7 +674,7 @@ mod test {
     #[should_panic(expected = "mock not found for a")]
     fn calls_real_impl_never() {
         let mut mock = Mock::<usize, String>::new("a");
-        mock.calls_real_impl(Matcher::Eq(3));
+        mock.calls_real_impl(Matcher::eq(3));
 
         mock.record_call_and_find_mock_output(2);
     }
@@ -167,7 +686,7 @@ mod test {
 
         mock.record_call_and_find_mock_output(3);
 
-        mock.assert_called(Matcher::Eq(3), Times::Exact(1));
+        mock.assert_called(Matcher::eq(3), Times::Exact(1));
     }
 
     #[test]
@@ -178,7 +697,7 @@ mod test {
 
         mock.record_call_and_find_mock_output(3);
 
-        mock.assert_called(Matcher::Eq(2), Times::Exact(1));
+        mock.assert_called(Matcher::eq(2), Times::Exact(1));
     }
 
     #[test]
@@ -187,11 +706,11 @@ mod test {
         let mut mock = Mock::<usize, String>::new("a");
         mock.returns_with(Matcher::Any, Behavior1::from(|a| "a".repeat(a)).into());
 
-        mock.assert_called(Matcher::Eq(3), Times::Exact(1));
+        mock.assert_called(Matcher::eq(3), Times::Exact(1));
     }
 
     #[test]
-    #[should_panic(expected = "[1, 2, 2]")]
+    #[should_panic(expected = "calls:\n  #0 1\n    ✗ arg0: 1 doesn't match Eq(3)\n  #1 2\n")]
     fn assert_called_with_log() {
         let mut mock = Mock::<usize, String>::new("a");
         mock.returns_with(Matcher::Any, Behavior1::from(|a| "a".repeat(a)).into());
@@ -199,7 +718,17 @@ mod test {
         mock.record_call_and_find_mock_output(2);
         mock.record_call_and_find_mock_output(2);
 
-        mock.assert_called(Matcher::Eq(3), Times::Exact(1));
+        mock.assert_called(Matcher::eq(3), Times::Exact(1));
+    }
+
+    #[test]
+    #[should_panic(expected = "matcher: AnyOf(Eq(3), Eq(4))")]
+    fn assert_called_with_composed_matcher() {
+        let mut mock = Mock::<usize, String>::new("a");
+        mock.returns_with(Matcher::Any, Behavior1::from(|a| "a".repeat(a)).into());
+        mock.record_call_and_find_mock_output(1);
+
+        mock.assert_called(crate::any_of![3, 4], Times::Exact(1));
     }
 
     #[test]
@@ -212,8 +741,8 @@ mod test {
         mock.record_call_and_find_mock_output(2);
 
         assert_eq!(
-            mock.assert_called(Matcher::Any, Times::Exact(3)),
-            Logs(vec![3, 3, 2]),
+            mock.assert_called(Matcher::Any, Times::Exact(3)).inputs(),
+            vec![3, 3, 2],
         );
     }
 
@@ -228,22 +757,93 @@ mod test {
         mock.record_call_and_find_mock_output(2);
 
         assert_eq!(
-            mock.assert_called(Matcher::Eq(2), Times::Exact(2)),
-            Logs(vec![2, 2]),
+            mock.assert_called(Matcher::eq(2), Times::Exact(2)).inputs(),
+            vec![2, 2],
+        );
+    }
+
+    #[test]
+    fn unused_rules() {
+        let mut mock = Mock::<usize, String>::new("a");
+        mock.returns(Matcher::eq(1), "1".into());
+        mock.returns(Matcher::eq(2), "2".into());
+        mock.returns(Matcher::eq(3), "3".into()).apply(RuleOptions {
+            expected: Some(0.into()),
+            ..Default::default()
+        });
+        mock.record_call_and_find_mock_output(1);
+
+        assert!(mock.unused_rules(false).is_empty());
+        assert_eq!(
+            mock.unused_rules(true),
+            vec!["a with Eq(2) never matched".to_string()]
+        );
+    }
+
+    #[test]
+    fn unused_rules_added_in_strict_scope() {
+        let mut mock = Mock::<usize, String>::new("a");
+        let location = std::panic::Location::caller();
+        mock.returns(Matcher::eq(1), "1".into());
+        {
+            let _strict = crate::strict();
+            mock.returns(Matcher::eq(2), "2".into()).apply(RuleOptions {
+                location: Some(location),
+                ..Default::default()
+            });
+        }
+
+        assert_eq!(
+            mock.unused_rules(false),
+            vec![format!("a with Eq(2) never matched, added at {}", location)]
+        );
+    }
+
+    #[test]
+    fn unmet_expectations_of_shadowed_rules() {
+        let mut mock = Mock::<usize, String>::new("a");
+        mock.returns(Matcher::Any, "any".into());
+        mock.returns(Matcher::eq(2), "2".into()).apply(RuleOptions {
+            expected: Some(1.into()),
+            ..Default::default()
+        });
+        mock.record_call_and_find_mock_output(2);
+
+        assert_eq!(
+            mock.unmet_expectations(),
+            vec!["a was expected to be called 1 times with Eq(2), but called 0 times, shadowed by an earlier rule matching any arguments: Any".to_string()]
+        );
+    }
+
+    #[test]
+    fn finite_iterators_do_not_shadow() {
+        let mut mock = Mock::<usize, String>::new("a");
+        let mut values = vec!["1".to_string()].into_iter();
+        mock.returns_iter(Matcher::Any, std::iter::from_fn(move || values.next()));
+        mock.returns(Matcher::eq(2), "2".into()).apply(RuleOptions {
+            expected: Some(2.into()),
+            ..Default::default()
+        });
+        mock.record_call_and_find_mock_output(2);
+        mock.record_call_and_find_mock_output(2);
+
+        assert_eq!(
+            mock.unmet_expectations(),
+            vec!["a was expected to be called 2 times with Eq(2), but called 1 times".to_string()]
         );
     }
 
     #[test]
     #[should_panic(
-        expected = "[Rule { matcher: Eq(3), behavior: Function(_) }, Rule { matcher: Eq(3), behavior: CallsRealImpl }]"
+        expected = "arguments: 2\nrules:\n  #0 Eq(3) => Function(_)\n    ✗ arg0: 2 doesn't match Eq(3)\n  #1 Eq(3) => CallsRealImpl\n"
     )]
     fn mock_not_found_with_rules() {
         let mut mock = Mock::<usize, String>::new("a");
         mock.returns_with(
-            Matcher::Eq(3),
+            Matcher::eq(3),
             Behavior::Function(Box::new(|_| "42".to_string())),
         );
-        mock.calls_real_impl(Matcher::Eq(3));
+        mock.calls_real_impl(Matcher::eq(3));
 
         mock.record_call_and_find_mock_output(2);
     }
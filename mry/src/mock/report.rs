@@ -0,0 +1,502 @@
+use std::fmt::{Debug, Write};
+use std::panic::Location;
+
+use crate::{times::Times, ArgMatch, Call, Matcher, Rule};
+
+/// Describes a failed `assert_called`, with each recorded call matched argument by argument,
+/// the rules of the method, and where it is asserted.
+/// Arguments are named by `params` if known, and calls are rendered by `describe_call`.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn assertion_failure<I: Debug, O: Debug>(
+    name: &str,
+    location: &Location<'_>,
+    params: &[&str],
+    matcher: &Matcher<I>,
+    times: Times,
+    matched: usize,
+    calls: &[Call<I>],
+    describe_call: &dyn Fn(&I) -> String,
+    rules: &[Rule<I, O>],
+) -> String {
+    let mut message = format!(
+        "{} was not called as expected\nmatcher: {:?}\ntimes: {}\nmatched: {}\ncalls:",
+        name, matcher, times, matched
+    );
+    if calls.is_empty() {
+        message.push_str(" none");
+    }
+    let arg_matches: Vec<_> = calls
+        .iter()
+        .map(|call| matcher.match_args(&call.input))
+        .collect();
+    for (index, (call, args)) in calls.iter().zip(&arg_matches).enumerate() {
+        write!(message, "\n  #{} {}", index, describe_call(&call.input)).unwrap();
+        for (position, arg) in args.iter().enumerate() {
+            let param = param(params, position);
+            if arg.matched {
+                write!(message, "\n    ✓ {}: {}", param, arg.input).unwrap();
+            } else {
+                write!(
+                    message,
+                    "\n    ✗ {}: {} doesn't match {}",
+                    param, arg.input, arg.matcher
+                )
+                .unwrap();
+            }
+        }
+    }
+    if let Some((index, args)) = closest(&arg_matches) {
+        write!(message, "\nclosest call #{}:", index).unwrap();
+        for (position, arg) in args.iter().enumerate().filter(|(_, arg)| !arg.matched) {
+            let param = param(params, position);
+            let expected = format!("- {}: {}", param, arg.matcher);
+            let actual = format!("+ {}: {}", param, arg.input);
+            write!(message, "\n  {}\n  {}", red(&expected), green(&actual)).unwrap();
+        }
+    }
+    if !rules.is_empty() {
+        message.push_str("\nrules:");
+        for (index, rule) in rules.iter().enumerate() {
+            message.push_str(&describe_rule(index, rule));
+        }
+    }
+    write!(message, "\nasserted at {}", location).unwrap();
+    message
+}
+
+/// Describes a call no rule matches, with which arguments each rule doesn't match and a hint.
+pub(crate) fn mock_not_found<I: Debug, O: Debug>(
+    name: &str,
+    params: &[&str],
+    input: &I,
+    rules: &[Rule<I, O>],
+    called_at: Option<&Location>,
+) -> String {
+    let mut message = format!(
+        "mock not found for {}\narguments: {:?}\nrules:",
+        name, input
+    );
+    if rules.is_empty() {
+        message.push_str(" none");
+    }
+    let mut used_up = false;
+    for (index, rule) in rules.iter().enumerate() {
+        message.push_str(&describe_rule(index, rule));
+        let args = rule.matcher.match_args(input);
+        if args.iter().all(|arg| arg.matched) {
+            if rule.is_retired() {
+                used_up = true;
+                message.push_str("\n    used up");
+            }
+            continue;
+        }
+        for (position, arg) in args.iter().enumerate().filter(|(_, arg)| !arg.matched) {
+            write!(
+                message,
+                "\n    ✗ {}: {} doesn't match {}",
+                param(params, position),
+                arg.input,
+                arg.matcher
+            )
+            .unwrap();
+        }
+    }
+    let mock_method = format!("mock_{}", name.rsplit("::").next().unwrap_or(name));
+    match rules.first() {
+        None => write!(message, "\nhint: did you forget to add a rule with {}?", mock_method),
+        Some(_) if used_up => write!(
+            message,
+            "\nhint: the rules matching the arguments are used up, did you forget to raise their times?"
+        ),
+        Some(rule) => {
+            let anys = vec!["Any"; rule.matcher.match_args(input).len()];
+            write!(
+                message,
+                "\nhint: did you forget {}({})?",
+                mock_method,
+                anys.join(", ")
+            )
+        }
+    }
+    .unwrap();
+    push_called_at(&mut message, called_at);
+    message
+}
+
+/// Describes a call only matching rules which have run out of values.
+pub(crate) fn sequence_exhausted<I: Debug, O: Debug>(
+    name: &str,
+    input: &I,
+    rules: &[Rule<I, O>],
+    called_at: Option<&Location>,
+) -> String {
+    let mut message = format!(
+        "sequence exhausted for {}\narguments: {:?}\nrules:",
+        name, input
+    );
+    for (index, rule) in rules.iter().enumerate() {
+        if rule.matcher.match_args(input).iter().all(|arg| arg.matched) {
+            message.push_str(&describe_rule(index, rule));
+        }
+    }
+    push_called_at(&mut message, called_at);
+    message
+}
+
+/// Appends where the mock is called if known, which is not for async methods
+fn push_called_at(message: &mut String, called_at: Option<&Location>) {
+    if let Some(location) = called_at {
+        write!(message, "\ncalled at {}", location).unwrap();
+    }
+}
+
+/// Notes the earlier rule which prevents a rule from matching in `RuleOrder::FirstWins`
+pub(crate) fn shadowed<I: Debug, O>(shadowing: &Rule<I, O>) -> String {
+    let mut note = format!(
+        ", shadowed by an earlier rule matching any arguments: {:?}",
+        shadowing.matcher
+    );
+    if let Some(location) = shadowing.location {
+        write!(note, " added at {}", location).unwrap();
+    }
+    note
+}
+
+/// The name of the parameter at the position, or `argN` if unknown
+fn param(params: &[&str], position: usize) -> String {
+    match params.get(position) {
+        Some(name) => name.to_string(),
+        None => format!("arg{}", position),
+    }
+}
+
+/// A line of a rule with its limit and where it is added
+fn describe_rule<I: Debug, O: Debug>(index: usize, rule: &Rule<I, O>) -> String {
+    let mut line = format!("\n  #{} {:?} => {:?}", index, rule.matcher, rule.behavior);
+    if let Some(limit) = rule.limit {
+        write!(line, " (limit: {}, hits: {})", limit, rule.hits).unwrap();
+    }
+    if let Some(location) = rule.location {
+        write!(line, ", added at {}", location).unwrap();
+    }
+    line
+}
+
+/// The first of the calls not matched with the most matched arguments
+fn closest(arg_matches: &[Vec<ArgMatch>]) -> Option<(usize, &Vec<ArgMatch>)> {
+    let matched_count = |args: &Vec<ArgMatch>| args.iter().filter(|arg| arg.matched).count();
+    arg_matches
+        .iter()
+        .enumerate()
+        .filter(|(_, args)| args.iter().any(|arg| !arg.matched))
+        .fold(None, |closest, (index, args)| match closest {
+            Some((_, closest_args)) if matched_count(closest_args) >= matched_count(args) => {
+                closest
+            }
+            _ => Some((index, args)),
+        })
+}
+
+fn red(text: &str) -> String {
+    colored("31", text)
+}
+
+fn green(text: &str) -> String {
+    colored("32", text)
+}
+
+/// Colors the text unless `NO_COLOR` is set
+fn colored(code: &str, text: &str) -> String {
+    if std::env::var_os("NO_COLOR").is_some() {
+        text.to_string()
+    } else {
+        format!("\x1b[{}m{}\x1b[0m", code, text)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::mock::Logs;
+    use crate::Behavior;
+
+    fn calls<I: Clone>(inputs: Vec<I>) -> Vec<Call<I>> {
+        let mut logs = Logs::default();
+        for input in inputs {
+            logs.push(input);
+        }
+        logs.0
+    }
+
+    fn location() -> &'static Location<'static> {
+        Location::caller()
+    }
+
+    fn debug<I: Debug>(input: &I) -> String {
+        format!("{:?}", input)
+    }
+
+    #[test]
+    fn not_called() {
+        let location = location();
+        assert_eq!(
+            assertion_failure::<u8, u8>(
+                "a",
+                location,
+                &[],
+                &Matcher::eq(3),
+                Times::Exact(1),
+                0,
+                &calls(vec![]),
+                &debug,
+                &[]
+            ),
+            format!(
+                "a was not called as expected\nmatcher: Eq(3)\ntimes: 1\nmatched: 0\ncalls: none\nasserted at {}",
+                location
+            )
+        );
+    }
+
+    #[test]
+    fn assertion_failure_shows_rules_with_locations() {
+        let location = location();
+        let mut rule = Rule::<u8, u8>::new(Matcher::eq(1), Behavior::values(std::iter::repeat(2)));
+        rule.location = Some(location);
+        let message = assertion_failure(
+            "a",
+            location,
+            &[],
+            &Matcher::eq(3),
+            Times::Exact(1),
+            0,
+            &calls(vec![]),
+            &debug,
+            &[rule],
+        );
+        assert!(message.ends_with(&format!(
+            "calls: none\nrules:\n  #0 Eq(1) => Const(2), added at {}\nasserted at {}",
+            location, location
+        )));
+    }
+
+    #[test]
+    fn per_argument_matches_and_closest_call() {
+        let matcher: Matcher<(u8, String)> = (Matcher::eq(1), Matcher::eq("a".into())).into();
+        let calls = calls(vec![(2, "b".to_string()), (1, "b".to_string())]);
+
+        assert_eq!(
+            assertion_failure::<_, u8>(
+                "a",
+                location(),
+                &[],
+                &matcher,
+                Times::Exact(1),
+                0,
+                &calls,
+                &debug,
+                &[]
+            ),
+            format!(
+                r#"a was not called as expected
+matcher: (Eq(1), Eq("a"))
+times: 1
+matched: 0
+calls:
+  #0 (2, "b")
+    ✗ arg0: 2 doesn't match Eq(1)
+    ✗ arg1: "b" doesn't match Eq("a")
+  #1 (1, "b")
+    ✓ arg0: 1
+    ✗ arg1: "b" doesn't match Eq("a")
+closest call #1:
+  {}
+  {}
+asserted at {}"#,
+                red(r#"- arg1: Eq("a")"#),
+                green(r#"+ arg1: "b""#),
+                location()
+            )
+        );
+    }
+
+    #[test]
+    fn names_arguments_and_describes_calls() {
+        let matcher: Matcher<(u8, String)> = (Matcher::eq(1), Matcher::eq("a".into())).into();
+        let calls = calls(vec![(1, "b".to_string())]);
+
+        let message = assertion_failure::<_, u8>(
+            "a",
+            location(),
+            &["count", "base"],
+            &matcher,
+            Times::Exact(1),
+            0,
+            &calls,
+            &|(count, base): &(u8, String)| {
+                format!("Call {{ count: {}, base: {:?} }}", count, base)
+            },
+            &[],
+        );
+        assert!(message.contains(&format!(
+            r#"calls:
+  #0 Call {{ count: 1, base: "b" }}
+    ✓ count: 1
+    ✗ base: "b" doesn't match Eq("a")
+closest call #0:
+  {}
+  {}"#,
+            red(r#"- base: Eq("a")"#),
+            green(r#"+ base: "b""#)
+        )));
+    }
+
+    #[test]
+    fn no_closest_call_if_all_matched() {
+        let message = assertion_failure::<_, u8>(
+            "a",
+            location(),
+            &[],
+            &Matcher::Any,
+            Times::Exact(1),
+            2,
+            &calls(vec![1, 2]),
+            &debug,
+            &[],
+        );
+        assert!(message.contains("#1 2\n    ✓ arg0: 2\nasserted at"));
+    }
+
+    #[test]
+    fn not_found_without_rules() {
+        assert_eq!(
+            mock_not_found::<u8, u8>("Cat::meow", &[], &3, &[], None),
+            "mock not found for Cat::meow\narguments: 3\nrules: none\nhint: did you forget to add a rule with mock_meow?"
+        );
+    }
+
+    #[test]
+    fn not_found_shows_failing_arguments() {
+        let rules: Vec<Rule<(u8, String), u8>> = vec![
+            Rule::new(
+                (Matcher::eq(1), Matcher::eq("a".to_string())).into(),
+                Behavior::values(std::iter::repeat(1)),
+            ),
+            Rule::new(
+                (Matcher::Any, Matcher::eq("b".to_string())).into(),
+                Behavior::CallsRealImpl,
+            ),
+        ];
+        assert_eq!(
+            mock_not_found(
+                "<Cat as Animal>::meow",
+                &[],
+                &(2, "c".to_string()),
+                &rules,
+                None
+            ),
+            r#"mock not found for <Cat as Animal>::meow
+arguments: (2, "c")
+rules:
+  #0 (Eq(1), Eq("a")) => Const(1)
+    ✗ arg0: 2 doesn't match Eq(1)
+    ✗ arg1: "c" doesn't match Eq("a")
+  #1 (Any, Eq("b")) => CallsRealImpl
+    ✗ arg1: "c" doesn't match Eq("b")
+hint: did you forget mock_meow(Any, Any)?"#
+        );
+    }
+
+    #[test]
+    fn not_found_names_arguments() {
+        let rules: Vec<Rule<(u8, String), u8>> = vec![Rule::new(
+            (Matcher::Any, Matcher::eq("a".to_string())).into(),
+            Behavior::CallsRealImpl,
+        )];
+        assert!(mock_not_found(
+            "meow",
+            &["count", "base"],
+            &(2, "c".to_string()),
+            &rules,
+            None
+        )
+        .contains("\n    ✗ base: \"c\" doesn't match Eq(\"a\")\n"));
+    }
+
+    #[test]
+    fn not_found_with_used_up_rules() {
+        let mut rule = Rule::<u8, u8>::new(Matcher::Any, Behavior::values(std::iter::repeat(1)));
+        rule.limit = Some(1);
+        rule.hits = 1;
+        assert_eq!(
+            mock_not_found("meow", &[], &3, &[rule], None),
+            "mock not found for meow\narguments: 3\nrules:\n  #0 Any => Const(1) (limit: 1, hits: 1)\n    used up\nhint: the rules matching the arguments are used up, did you forget to raise their times?"
+        );
+    }
+
+    #[test]
+    fn not_found_shows_rule_locations() {
+        let location = location();
+        let mut rule = Rule::<u8, u8>::new(Matcher::eq(1), Behavior::values(std::iter::repeat(1)));
+        rule.location = Some(location);
+        assert!(
+            mock_not_found("meow", &[], &3, &[rule], None).contains(&format!(
+                "\n  #0 Eq(1) => Const(1), added at {}\n    ✗ arg0: 3 doesn't match Eq(1)",
+                location
+            ))
+        );
+    }
+
+    #[test]
+    fn not_found_shows_where_it_is_called() {
+        let location = location();
+        assert_eq!(
+            mock_not_found::<u8, u8>("meow", &[], &3, &[], Some(location)),
+            format!(
+                "mock not found for meow\narguments: 3\nrules: none\nhint: did you forget to add a rule with mock_meow?\ncalled at {}",
+                location
+            )
+        );
+    }
+
+    #[test]
+    fn sequence_exhausted_shows_where_it_is_called() {
+        let location = location();
+        assert!(
+            sequence_exhausted::<u8, u8>("meow", &3, &[], Some(location))
+                .ends_with(&format!("\ncalled at {}", location))
+        );
+    }
+
+    #[test]
+    fn sequence_exhausted_shows_matching_rules() {
+        let location = location();
+        let mut rules = vec![
+            Rule::<u8, u8>::new(Matcher::eq(1), Behavior::values(std::iter::repeat(1))),
+            Rule::<u8, u8>::new(Matcher::eq(3), Behavior::values(std::iter::empty())),
+        ];
+        rules[1].location = Some(location);
+        assert_eq!(
+            sequence_exhausted("meow", &3, &rules, None),
+            format!(
+                "sequence exhausted for meow\narguments: 3\nrules:\n  #1 Eq(3) => Const(<exhausted>), added at {}",
+                location
+            )
+        );
+    }
+
+    #[test]
+    fn closest_is_the_first_of_ties() {
+        let arg = |matched| ArgMatch {
+            matched,
+            input: String::new(),
+            matcher: String::new(),
+        };
+        let arg_matches = vec![
+            vec![arg(true), arg(false)],
+            vec![arg(false), arg(true)],
+            vec![arg(false), arg(false)],
+        ];
+        assert_eq!(closest(&arg_matches).map(|(index, _)| index), Some(0));
+    }
+}
@@ -1,15 +1,26 @@
 pub mod times;
 
+use std::future::Future;
 use std::marker::PhantomData;
+use std::panic::Location;
+use std::sync::Arc;
+use std::time::Duration;
 use std::{any::TypeId, fmt::Debug};
 
-use crate::mock::Mock;
-use crate::{Behavior, Matcher, MockGetter};
+use parking_lot::Mutex;
+
+use crate::delay::Delay;
+use crate::mock::{MethodFallback, Mock};
+use crate::{
+    Behavior, Call, Fallback, Matcher, MockGetter, RealImpl, RuleOptions, RuleOrder, WithMut,
+};
 
 use self::times::Times;
 
-/// Mock locator returned by mock_* methods
-pub struct MockLocator<'a, I, O, B> {
+/// Mock locator returned by mock_* methods.
+/// `C` is the type of call records returned by `assert_called` and `calls`, which has the arguments as named fields.
+/// Panics of calls without a matching rule report where the method is called, except for async methods.
+pub struct MockLocator<'a, I, O, B, C = I> {
     #[doc(hidden)]
     pub mocks: Box<dyn MockGetter<I, O> + 'a>,
     #[doc(hidden)]
@@ -17,67 +28,366 @@ pub struct MockLocator<'a, I, O, B> {
     #[doc(hidden)]
     pub name: &'static str,
     #[doc(hidden)]
+    pub params: &'static [&'static str],
+    #[doc(hidden)]
     pub matcher: Option<Matcher<I>>,
     #[doc(hidden)]
-    pub _phantom: PhantomData<fn() -> (I, O, B)>,
+    pub options: RuleOptions,
+    #[doc(hidden)]
+    pub _phantom: PhantomData<fn() -> (I, O, B, C)>,
 }
 
-impl<'a, I, O, B> MockLocator<'a, I, O, B>
+impl<'a, I, O, B, C> MockLocator<'a, I, O, B, C>
 where
-    I: Clone + PartialEq + Debug + Send + Sync + 'static,
+    I: Clone + Debug + Send + Sync + 'static,
     O: Debug + Send + Sync + 'static,
     B: Into<Behavior<I, O>>,
 {
     /// Returns value with using a clojure.
     /// Arguments of a method call are passed to the given clojure.
+    #[track_caller]
     pub fn returns_with<T: Into<B>>(&mut self, behavior: T) {
-        let matcher = self.matcher();
+        self.returns_behavior(behavior.into().into());
+    }
+
+    /// Returns value with using a clojure that receives mutable references to the arguments.
+    /// Changes to `&mut` arguments are written back to the caller, while logs keep the arguments before the call.
+    #[track_caller]
+    pub fn returns_with_mut<T>(&mut self, behavior: T)
+    where
+        B: WithMut,
+        T: Into<B::Mut>,
+        B::Mut: Into<Behavior<I, O>>,
+    {
+        self.returns_behavior(behavior.into().into());
+    }
+
+    /// This makes the mock return the given values in order, one for each call.
+    /// When they run out, the next matching rule is used or it panics.
+    /// Unlike `returns`, this doesn't require `Clone`.
+    #[track_caller]
+    pub fn returns_seq(&mut self, values: Vec<O>) {
+        self.returns_iter(values)
+    }
+
+    /// This makes the mock return the given value only once.
+    /// After that, the next matching rule is used or it panics.
+    #[track_caller]
+    pub fn returns_once(&mut self, value: O) {
+        let (matcher, options) = (self.matcher(), self.rule_options());
+        self.get_mut_or_default()
+            .returns_once(matcher, value)
+            .apply(options);
+    }
+
+    /// This makes the mock return the values of the iterator in order, one for each call.
+    /// When it runs out, the next matching rule is used or it panics.
+    #[track_caller]
+    pub fn returns_iter<T>(&mut self, iter: T)
+    where
+        T: IntoIterator<Item = O>,
+        T::IntoIter: Send + Sync + 'static,
+    {
+        let (matcher, options) = (self.matcher(), self.rule_options());
+        self.get_mut_or_default()
+            .returns_iter(matcher, iter.into_iter())
+            .apply(options);
+    }
+
+    /// This makes the mock return the output of the future returned by the given clojure.
+    /// Arguments of a method call are passed to the given clojure.
+    /// The future is awaited in the caller's future, so this is only for async methods.
+    #[track_caller]
+    pub fn returns_async<F, Fut>(&mut self, mut behavior: F)
+    where
+        F: FnMut(I) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = O> + Send + 'static,
+    {
+        self.returns_behavior(Behavior::Async(Box::new(move |input| {
+            Box::pin(behavior(input))
+        })));
+    }
+
+    /// This makes the mock return the given value after the duration without blocking the thread.
+    /// This is only for async methods and doesn't depend on a specific runtime.
+    #[track_caller]
+    pub fn returns_after(&mut self, duration: Duration, value: O)
+    where
+        O: Clone,
+    {
+        self.returns_async(move |_| {
+            let value = value.clone();
+            async move {
+                Delay::new(duration).await;
+                value
+            }
+        })
+    }
+
+    /// This makes the mock panic with the given message as the payload.
+    #[track_caller]
+    pub fn panics(&mut self, message: impl Into<String>) {
+        self.returns_behavior(Behavior::Panics(message.into()));
+    }
+
+    /// This makes the mock panic with the message returned by the given clojure as the payload.
+    /// Arguments of a method call are passed to the given clojure.
+    #[track_caller]
+    pub fn panics_with(&mut self, message: impl FnMut(I) -> String + Send + Sync + 'static) {
+        self.returns_behavior(Behavior::PanicsWith(Box::new(message)));
+    }
+
+    /// This makes the mock call real impl with the arguments returned by the given clojure.
+    /// This is not supported for methods taking references.
+    #[track_caller]
+    pub fn calls_real_impl_with(&mut self, function: impl FnMut(I) -> I + Send + Sync + 'static) {
+        self.returns_behavior(Behavior::CallsRealImplWith(Box::new(function)));
+    }
+
+    /// This makes the mock call the given clojure with the arguments and the real impl,
+    /// to run code before and after the real impl and post-process its return value.
+    /// This is not supported for async methods and methods taking references.
+    #[track_caller]
+    pub fn wraps_real_impl(
+        &mut self,
+        function: impl for<'r> FnMut(I, RealImpl<'r, I, O>) -> O + Send + 'static,
+    ) {
+        self.returns_behavior(Behavior::WrapsRealImpl(Arc::new(Mutex::new(Box::new(
+            function,
+        )))));
+    }
+
+    /// Removes the earlier rules with a matcher equivalent to the one of `mock_*`,
+    /// so the next rule replaces them.
+    /// Only matchers made of `Any` and values are compared, and rules with other matchers are kept.
+    pub fn replace(&mut self) -> &mut Self {
+        let matcher = self.matcher.take().unwrap();
+        self.get_mut_or_default().replace(&matcher);
+        self.matcher = Some(matcher);
+        self
+    }
+
+    /// Sets which of the rules matching a call is used, regardless of the arguments of `mock_*`.
+    /// This takes precedence over `mry::default_rule_order`.
+    pub fn rule_order(&mut self, order: RuleOrder) -> &mut Self {
+        self.get_mut_or_default().set_rule_order(order);
+        self
+    }
+
+    /// Removes the rules, logs, and settings of the method, regardless of the arguments of `mock_*`.
+    pub fn reset(&mut self) {
+        self.get_mut_or_default().reset();
+    }
+
+    /// Removes the logs of the method, keeping the rules.
+    pub fn clear_logs(&mut self) {
+        self.get_mut_or_default().clear_logs();
+    }
+
+    /// Sets what the mock does for calls no rule matches, regardless of the arguments of `mock_*`.
+    /// This takes precedence over the fallback of the object and `mry::default_fallback`.
+    pub fn fallback(&mut self, fallback: Fallback) {
         self.get_mut_or_default()
-            .returns_with(matcher, behavior.into().into());
+            .fallback(MethodFallback::Fallback(fallback));
+    }
+
+    /// Makes the mock return a value with using a clojure for calls no rule matches,
+    /// regardless of the arguments of `mock_*`.
+    pub fn fallback_with<T: Into<B>>(&mut self, behavior: T) {
+        self.get_mut_or_default()
+            .fallback(MethodFallback::Behavior(behavior.into().into()));
     }
 
     /// This make the mock calls real impl. This is used for partial mocking.
+    #[track_caller]
     pub fn calls_real_impl(&mut self) {
-        let matcher = self.matcher();
-        self.get_mut_or_default().calls_real_impl(matcher);
+        let (matcher, options) = (self.matcher(), self.rule_options());
+        self.get_mut_or_default()
+            .calls_real_impl(matcher)
+            .apply(options);
     }
 
     /// Assert the mock is called.
-    /// Returns `MockResult` allows to call `times(n)`
-    /// Panics if not called
-    pub fn assert_called(&mut self, times: impl Into<Times>) -> Vec<I> {
+    /// Panics if not called, and returns the records of the calls matching the mock
+    #[track_caller]
+    pub fn assert_called(&mut self, times: impl Into<Times>) -> Vec<C>
+    where
+        C: From<I> + Debug,
+    {
         let matcher = self.matcher.take().unwrap();
-        self.get_or_error().assert_called(matcher, times.into()).0
+        self.get_or_error()
+            .assert_called_as::<C>(matcher, times.into(), self.params)
+            .inputs()
+            .into_iter()
+            .map(C::from)
+            .collect()
+    }
+
+    /// Returns the calls matching the mock with their outputs and metadata, for assertions and debugging.
+    pub fn calls(&mut self) -> Vec<Call<C>>
+    where
+        C: From<I>,
+    {
+        let matcher = self.matcher();
+        self.mocks
+            .get(&self.key, self.name)
+            .map(|mock| mock.calls(&matcher))
+            .unwrap_or_default()
+            .into_iter()
+            .map(Call::map_input)
+            .collect()
+    }
+
+    /// Describes the mock and returns the sequences of the calls matching it, used by `InOrder`.
+    pub(crate) fn sequences(&mut self) -> (String, Vec<u64>) {
+        let matcher = self.matcher();
+        let description = format!("{} with {:?}", self.name, matcher);
+        let sequences = self
+            .mocks
+            .get(&self.key, self.name)
+            .map(|mock| mock.sequences(&matcher))
+            .unwrap_or_default();
+        (description, sequences)
+    }
+
+    /// Assert all rules limited by `times` or `once` of the mock have been used up.
+    /// Panics if some of them matched fewer times than the limit.
+    #[track_caller]
+    pub fn assert_consumed(&mut self) {
+        self.get_or_error().assert_consumed()
     }
 }
 
-impl<'a, I, O, B> MockLocator<'a, I, O, B>
+impl<'a, I, O, B, C> MockLocator<'a, I, O, B, C>
 where
-    I: Clone + PartialEq + Debug + Send + Sync + 'static,
+    I: Clone + Debug + Send + Sync + 'static,
     O: Clone + Debug + Send + Sync + 'static,
 {
     /// This makes the mock returns the given constant value.
     /// This requires `Clone`. For returning not clone value, use `returns_with`.
+    #[track_caller]
     pub fn returns(&mut self, ret: O) {
-        let matcher = self.matcher();
-        self.get_mut_or_default().returns(matcher, ret);
+        let (matcher, options) = (self.matcher(), self.rule_options());
+        self.get_mut_or_default()
+            .returns(matcher, ret)
+            .apply(options);
+    }
+}
+
+impl<'a, I, T, E, B, C> MockLocator<'a, I, Result<T, E>, B, C>
+where
+    I: Clone + Debug + Send + Sync + 'static,
+    T: Debug + Send + Sync + 'static,
+    E: Debug + Send + Sync + 'static,
+{
+    /// This makes the mock return `Ok` with the given value.
+    #[track_caller]
+    pub fn returns_ok(&mut self, value: T)
+    where
+        T: Clone,
+    {
+        self.returns_behavior(Behavior::Function(Box::new(move |_| Ok(value.clone()))));
+    }
+
+    /// This makes the mock return `Err` with the given error.
+    #[track_caller]
+    pub fn returns_err(&mut self, error: E)
+    where
+        E: Clone,
+    {
+        self.returns_behavior(Behavior::Function(Box::new(move |_| Err(error.clone()))));
+    }
+
+    /// This makes the mock return `Err` with an error created by the given factory on each call.
+    /// This doesn't require `Clone` unlike `returns_err`.
+    #[track_caller]
+    pub fn returns_err_with(&mut self, mut factory: impl FnMut() -> E + Send + Sync + 'static) {
+        self.returns_behavior(Behavior::Function(Box::new(move |_| Err(factory()))));
+    }
+}
+
+impl<'a, I, T, B, C> MockLocator<'a, I, Option<T>, B, C>
+where
+    I: Clone + Debug + Send + Sync + 'static,
+    T: Debug + Send + Sync + 'static,
+{
+    /// This makes the mock return `Some` with the given value.
+    #[track_caller]
+    pub fn returns_some(&mut self, value: T)
+    where
+        T: Clone,
+    {
+        self.returns_behavior(Behavior::Function(Box::new(move |_| Some(value.clone()))));
+    }
+
+    /// This makes the mock return `None`.
+    #[track_caller]
+    pub fn returns_none(&mut self) {
+        self.returns_behavior(Behavior::Function(Box::new(|_| None)));
+    }
+}
+
+impl<'a, I, O, B, C> MockLocator<'a, I, O, B, C>
+where
+    I: Clone + Debug + Send + Sync + 'static,
+    O: Debug + Send + Sync + 'static,
+{
+    #[track_caller]
+    fn returns_behavior(&mut self, behavior: Behavior<I, O>) {
+        let (matcher, options) = (self.matcher(), self.rule_options());
+        self.get_mut_or_default()
+            .returns_with(matcher, behavior)
+            .apply(options);
+    }
+}
+
+impl<'a, I, O, B, C> MockLocator<'a, I, O, B, C> {
+    /// Limits the next rule to match only `n` times.
+    /// After that, the next matching rule is used or it panics.
+    pub fn times(&mut self, n: usize) -> &mut Self {
+        self.options.limit = Some(n);
+        self
+    }
+
+    /// Limits the next rule to match only once.
+    pub fn once(&mut self) -> &mut Self {
+        self.times(1)
+    }
+
+    /// Options of the next rule with the location of the caller
+    #[track_caller]
+    fn rule_options(&self) -> RuleOptions {
+        RuleOptions {
+            location: Some(Location::caller()),
+            ..self.options
+        }
+    }
+
+    /// Expects the next rule to match the given times.
+    /// This is verified when the object, or the lock of a static function, is dropped.
+    pub fn expect(&mut self, times: impl Into<Times>) -> &mut Self {
+        self.options.expected = Some(times.into());
+        self
     }
 }
 
-impl<'a, I, O, B> MockLocator<'a, I, O, B>
+impl<'a, I, O, B, C> MockLocator<'a, I, O, B, C>
 where
     I: Send + Sync + 'static,
     O: Send + Sync + 'static,
 {
     fn get_mut_or_default(&mut self) -> &mut Mock<I, O> {
-        self.mocks.get_mut_or_create(self.key.clone(), &self.name)
+        let mock = self.mocks.get_mut_or_create(self.key.clone(), &self.name);
+        mock.params = self.params;
+        mock
     }
 }
-impl<'a, I, O, B> MockLocator<'a, I, O, B>
+impl<'a, I, O, B, C> MockLocator<'a, I, O, B, C>
 where
     I: Send + Sync + 'static,
     O: Send + Sync + 'static,
 {
+    #[track_caller]
     fn get_or_error(&self) -> &Mock<I, O> {
         self.mocks
             .get(&self.key, &self.name)
@@ -1,7 +1,8 @@
+use std::fmt::Display;
 use std::ops::{Bound, Range, RangeBounds, RangeFrom, RangeInclusive, RangeTo};
 
 #[doc(hidden)]
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 pub enum Times {
     Exact(usize),
     Range((Bound<usize>, Bound<usize>)),
@@ -16,6 +17,24 @@ impl Times {
     }
 }
 
+impl Display for Times {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Times::Exact(n) => write!(f, "{}", n),
+            Times::Range((start, end)) => {
+                if let Bound::Included(start) = start {
+                    write!(f, "{}", start)?;
+                }
+                match end {
+                    Bound::Included(end) => write!(f, "..={}", end),
+                    Bound::Excluded(end) => write!(f, "..{}", end),
+                    Bound::Unbounded => write!(f, ".."),
+                }
+            }
+        }
+    }
+}
+
 impl From<usize> for Times {
     fn from(times: usize) -> Self {
         Times::Exact(times)
@@ -67,4 +86,13 @@ mod tests {
         assert_eq!(times.contains(&4), false);
         assert_eq!(times.contains(&5), false);
     }
+
+    #[test]
+    fn display() {
+        assert_eq!(Times::from(2).to_string(), "2");
+        assert_eq!(Times::from(1..3).to_string(), "1..3");
+        assert_eq!(Times::from(1..=3).to_string(), "1..=3");
+        assert_eq!(Times::from(1..).to_string(), "1..");
+        assert_eq!(Times::from(..3).to_string(), "..3");
+    }
 }
@@ -0,0 +1,155 @@
+use std::fmt::Debug;
+use std::future::Future;
+
+use crate::mock::RecordedOutput;
+use crate::Output;
+
+/// Output of a mock for a call, which is resolved with the real implementation if needed
+#[doc(hidden)]
+pub struct MockOutput<I, O> {
+    name: &'static str,
+    output: Output<I, O>,
+    recorded: Option<RecordedOutput>,
+}
+
+impl<I, O> MockOutput<I, O> {
+    pub(crate) fn new(name: &'static str, output: Output<I, O>) -> Self {
+        Self {
+            name,
+            output,
+            recorded: None,
+        }
+    }
+
+    /// Records the output produced later in the log of the call.
+    pub(crate) fn recording(mut self, recorded: RecordedOutput) -> Self {
+        self.recorded = Some(recorded);
+        self
+    }
+
+    fn record(recorded: Option<RecordedOutput>, output: O) -> O
+    where
+        O: Debug,
+    {
+        if let Some(recorded) = recorded {
+            let _ = recorded.set(format!("{:?}", output));
+        }
+        output
+    }
+
+    pub(crate) fn real_impl(name: &'static str) -> Self {
+        Self::new(name, Output::CallsRealImpl)
+    }
+
+    /// Returns the output of the mock, or calls the real implementation with the input.
+    pub fn or_real_impl(self, input: I, real: impl FnOnce(I) -> O) -> O
+    where
+        O: Debug,
+    {
+        let name = self.name;
+        let output = match self.output {
+            Output::Found(output) => output,
+            Output::CallsRealImplWith(input) => real(input),
+            Output::WrapsRealImpl(function) => {
+                let mut function = function
+                    .try_lock()
+                    .unwrap_or_else(|| panic!("{} is called recursively in wraps_real_impl", name));
+                function(input, Box::new(real))
+            }
+            Output::Pending(_) => {
+                panic!("{} returns a future, but it is not an async method", name)
+            }
+            _ => real(input),
+        };
+        Self::record(self.recorded, output)
+    }
+
+    /// Awaits the output of the mock, or calls the real implementation with the input.
+    pub async fn or_real_impl_async<F, Fut>(self, input: I, real: F) -> O
+    where
+        F: FnOnce(I) -> Fut,
+        Fut: Future<Output = O>,
+        O: Debug,
+    {
+        let name = self.name;
+        let output = match self.output {
+            Output::Found(output) => output,
+            Output::Pending(future) => future.await,
+            Output::CallsRealImplWith(input) => real(input).await,
+            Output::WrapsRealImpl(_) => panic!(
+                "wraps_real_impl is not supported for {} because it is an async method",
+                name
+            ),
+            _ => real(input).await,
+        };
+        Self::record(self.recorded, output)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use parking_lot::Mutex;
+
+    use super::*;
+
+    #[test]
+    fn found() {
+        let output = MockOutput::new("a", Output::Found(3));
+
+        assert_eq!(output.or_real_impl(1, |a: u8| a), 3);
+    }
+
+    #[test]
+    fn calls_real_impl() {
+        let output = MockOutput::<u8, u8>::real_impl("a");
+
+        assert_eq!(output.or_real_impl(1, |a| a + 1), 2);
+    }
+
+    #[test]
+    fn calls_real_impl_with() {
+        let output = MockOutput::<u8, u8>::new("a", Output::CallsRealImplWith(5));
+
+        assert_eq!(output.or_real_impl(1, |a| a + 1), 6);
+    }
+
+    #[test]
+    fn wraps_real_impl() {
+        let output = MockOutput::<u8, u8>::new(
+            "a",
+            Output::WrapsRealImpl(Arc::new(Mutex::new(Box::new(|a, real| real(a + 1) * 10)))),
+        );
+
+        assert_eq!(output.or_real_impl(1, |a| a + 1), 30);
+    }
+
+    #[test]
+    #[should_panic(expected = "a returns a future, but it is not an async method")]
+    fn pending_in_sync() {
+        let output = MockOutput::<u8, u8>::new("a", Output::Pending(Box::pin(async { 1 })));
+
+        output.or_real_impl(1, |a| a);
+    }
+
+    #[test]
+    fn pending() {
+        let output = MockOutput::<u8, u8>::new("a", Output::Pending(Box::pin(async { 1 })));
+
+        assert_eq!(
+            async_std::task::block_on(output.or_real_impl_async(3, |a| async move { a })),
+            1
+        );
+    }
+
+    #[test]
+    fn calls_real_impl_async() {
+        let output = MockOutput::<u8, u8>::new("a", Output::CallsRealImplWith(5));
+
+        assert_eq!(
+            async_std::task::block_on(output.or_real_impl_async(3, |a| async move { a })),
+            5
+        );
+    }
+}
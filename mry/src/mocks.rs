@@ -2,10 +2,46 @@ use std::any::{Any, TypeId};
 use std::collections::HashMap;
 use std::fmt::Debug;
 use std::ops::{Deref, DerefMut};
+use std::panic::Location;
 
 use crate::mock::Mock;
+use crate::{BoxFuture, Fallback, MockOutput};
 
-type BoxAnySend = Box<dyn Any + Send + Sync>;
+/// A mock whose input and output types are erased
+pub(crate) trait AnyMock: Send + Sync {
+    fn as_any(&self) -> &dyn Any;
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+    fn reset(&mut self);
+    fn clear_logs(&mut self);
+    fn unmet_expectations(&self) -> Vec<String>;
+    fn unused_rules(&self, strict: bool) -> Vec<String>;
+}
+
+impl<I: Debug + Send + Sync + 'static, O: Send + Sync + 'static> AnyMock for Mock<I, O> {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn reset(&mut self) {
+        Mock::reset(self)
+    }
+
+    fn clear_logs(&mut self) {
+        Mock::clear_logs(self)
+    }
+
+    fn unmet_expectations(&self) -> Vec<String> {
+        Mock::unmet_expectations(self)
+    }
+
+    fn unused_rules(&self, strict: bool) -> Vec<String> {
+        Mock::unused_rules(self, strict)
+    }
+}
 
 #[doc(hidden)]
 pub trait MockGetter<I, O> {
@@ -30,42 +66,131 @@ where
 #[derive(Default)]
 #[doc(hidden)]
 pub struct Mocks {
-    pub(crate) mock_objects: HashMap<TypeId, BoxAnySend>,
+    pub(crate) mock_objects: HashMap<TypeId, Box<dyn AnyMock>>,
+    pub(crate) fallback: Option<Fallback>,
+    /// Whether all rules of the object are reported if they never match
+    pub(crate) strict: bool,
 }
 
-impl<I: Send + Sync + 'static, O: 'static> MockGetter<I, O> for Mocks {
+impl<I: Debug + Send + Sync + 'static, O: Send + Sync + 'static> MockGetter<I, O> for Mocks {
     fn get(&self, key: &TypeId, _name: &'static str) -> Option<&Mock<I, O>> {
         self.mock_objects
             .get(key)
-            .map(|mock| mock.downcast_ref().unwrap())
+            .map(|mock| mock.as_any().downcast_ref().unwrap())
     }
 
     fn get_mut_or_create(&mut self, key: TypeId, name: &'static str) -> &mut Mock<I, O> {
-        self.mock_objects
+        let mock: &mut Mock<I, O> = self
+            .mock_objects
             .entry(key)
             .or_insert(Box::new(Mock::<I, O>::new(name)))
+            .as_any_mut()
             .downcast_mut()
-            .unwrap()
+            .unwrap();
+        mock.object_fallback = self.fallback;
+        mock
     }
 }
 
 impl Mocks {
     #[doc(hidden)]
     pub fn record_call_and_find_mock_output<
-        I: PartialEq + Debug + Clone + Send + Sync + 'static,
+        I: Debug + Clone + Send + Sync + 'static,
         O: Debug + Send + Sync + 'static,
     >(
         &mut self,
         key: TypeId,
         name: &'static str,
         input: I,
+        default: Option<fn() -> O>,
+        location: Option<&'static Location<'static>>,
     ) -> Option<O> {
-        self.get_mut_or_create(key, name)
+        self.get_mut_or_create_with_default(key, name, default, location)
             .record_call_and_find_mock_output(input)
     }
 
+    #[doc(hidden)]
+    pub fn record_call_and_find_mock_future<
+        I: Debug + Clone + Send + Sync + 'static,
+        O: Debug + Send + Sync + 'static,
+    >(
+        &mut self,
+        key: TypeId,
+        name: &'static str,
+        input: I,
+        default: Option<fn() -> O>,
+        location: Option<&'static Location<'static>>,
+    ) -> Option<BoxFuture<O>> {
+        self.get_mut_or_create_with_default(key, name, default, location)
+            .record_call_and_find_mock_future(input)
+    }
+
+    #[doc(hidden)]
+    pub fn record_call_and_find_real_impl<
+        I: Debug + Clone + Send + Sync + 'static,
+        O: Debug + Send + Sync + 'static,
+    >(
+        &mut self,
+        key: TypeId,
+        name: &'static str,
+        input: I,
+        default: Option<fn() -> O>,
+        location: Option<&'static Location<'static>>,
+    ) -> MockOutput<I, O> {
+        self.get_mut_or_create_with_default(key, name, default, location)
+            .record_call_and_find_real_impl(input)
+    }
+
+    #[doc(hidden)]
+    pub fn record_call_and_find_mock_output_mut<
+        I: Debug + Clone + Send + Sync + 'static,
+        O: Debug + Send + Sync + 'static,
+    >(
+        &mut self,
+        key: TypeId,
+        name: &'static str,
+        input: &mut I,
+        default: Option<fn() -> O>,
+        location: Option<&'static Location<'static>>,
+    ) -> Option<O> {
+        self.get_mut_or_create_with_default(key, name, default, location)
+            .record_call_and_find_mock_output_mut(input)
+    }
+
+    #[doc(hidden)]
+    pub fn record_call_and_find_mock_future_mut<
+        I: Debug + Clone + Send + Sync + 'static,
+        O: Debug + Send + Sync + 'static,
+    >(
+        &mut self,
+        key: TypeId,
+        name: &'static str,
+        input: &mut I,
+        default: Option<fn() -> O>,
+        location: Option<&'static Location<'static>>,
+    ) -> Option<BoxFuture<O>> {
+        self.get_mut_or_create_with_default(key, name, default, location)
+            .record_call_and_find_mock_future_mut(input)
+    }
+
+    fn get_mut_or_create_with_default<
+        I: Debug + Send + Sync + 'static,
+        O: Send + Sync + 'static,
+    >(
+        &mut self,
+        key: TypeId,
+        name: &'static str,
+        default: Option<fn() -> O>,
+        location: Option<&'static Location<'static>>,
+    ) -> &mut Mock<I, O> {
+        let mock = self.get_mut_or_create(key, name);
+        mock.default = default;
+        mock.called_at = location;
+        mock
+    }
+
     #[cfg(test)]
-    pub(crate) fn insert<I: Send + Sync + 'static, O: 'static>(
+    pub(crate) fn insert<I: Debug + Send + Sync + 'static, O: Send + Sync + 'static>(
         &mut self,
         key: TypeId,
         item: Mock<I, O>,
@@ -73,11 +198,67 @@ impl Mocks {
         self.mock_objects.insert(key, Box::new(item));
     }
 
-    pub(crate) fn remove(&mut self, key: &TypeId) -> Option<()> {
-        self.mock_objects.remove(key).map(|_| ())
+    /// Resets all mocks, keeping the fallback of the object.
+    pub(crate) fn reset_all(&mut self) {
+        self.mock_objects.values_mut().for_each(|mock| mock.reset());
+    }
+
+    pub(crate) fn clear_logs(&mut self) {
+        self.mock_objects
+            .values_mut()
+            .for_each(|mock| mock.clear_logs());
+    }
+
+    /// Removes the mock, and panics if it has unmet expectations or unused strict rules.
+    /// Removes the mock and returns its unmet expectations and unused rules to be verified by the caller.
+    pub(crate) fn remove(&mut self, key: &TypeId) -> Option<(Vec<String>, Vec<String>)> {
+        let mock = self.mock_objects.remove(key)?;
+        Some((mock.unmet_expectations(), mock.unused_rules(self.strict)))
+    }
+}
+
+impl Drop for Mocks {
+    fn drop(&mut self) {
+        let mut unmet: Vec<_> = self
+            .mock_objects
+            .values()
+            .flat_map(|mock| mock.unmet_expectations())
+            .collect();
+        let mut unused: Vec<_> = self
+            .mock_objects
+            .values()
+            .flat_map(|mock| mock.unused_rules(self.strict))
+            .collect();
+        // Sorted because the order of mocks is random
+        unmet.sort();
+        unused.sort();
+        verify(unmet, unused);
+    }
+}
+
+/// Panics with all of the unmet expectations and unused rules, unless the thread is already panicking.
+fn verify(unmet: Vec<String>, unused: Vec<String>) {
+    if std::thread::panicking() {
+        return;
+    }
+    let sections = report(unmet, unused);
+    if !sections.is_empty() {
+        panic!("{}", sections.join("\n"))
     }
 }
 
+/// Sections of the unmet expectations and unused rules, empty if all are verified
+pub(crate) fn report(unmet: Vec<String>, unused: Vec<String>) -> Vec<String> {
+    let mut sections = Vec::new();
+    if !unmet.is_empty() {
+        sections.push(format!("unmet expectations:\n{}", unmet.join("\n")));
+    }
+    if !unused.is_empty() {
+        sections.push(format!("unused rules:\n{}", unused.join("\n")));
+    }
+    sections
+}
+
 #[cfg(test)]
 mod test {
     use crate::{Behavior, Matcher};
@@ -126,4 +307,47 @@ mod test {
             &"meow",
         );
     }
+
+    #[test]
+    #[should_panic(expected = "unused rules:\nmeow with Any never matched")]
+    fn drop_reports_unused_rules_if_strict() {
+        let mut mock_data = Mocks::default();
+        mock_data.strict = true;
+        MockGetter::<usize, usize>::get_mut_or_create(
+            &mut mock_data,
+            TypeId::of::<usize>(),
+            "meow",
+        )
+        .returns(Matcher::Any, 1);
+    }
+
+    #[test]
+    // should not panic
+    fn drop_ignores_unused_rules_if_not_strict() {
+        let mut mock_data = Mocks::default();
+        MockGetter::<usize, usize>::get_mut_or_create(
+            &mut mock_data,
+            TypeId::of::<usize>(),
+            "meow",
+        )
+        .returns(Matcher::Any, 1);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "unmet expectations:\nmeow was expected to be called 1 times with Any, but called 0 times"
+    )]
+    fn drop_verifies_expectations() {
+        let mut mock_data = Mocks::default();
+        MockGetter::<usize, usize>::get_mut_or_create(
+            &mut mock_data,
+            TypeId::of::<usize>(),
+            "meow",
+        )
+        .returns(Matcher::Any, 1)
+        .apply(crate::RuleOptions {
+            expected: Some(1.into()),
+            ..Default::default()
+        });
+    }
 }
@@ -3,6 +3,7 @@ use parking_lot::RwLock;
 use std::any::TypeId;
 use std::cmp::Ordering;
 use std::fmt::Debug;
+use std::panic::Location;
 #[cfg(debug_assertions)]
 use std::sync::atomic::AtomicU16;
 #[cfg(debug_assertions)]
@@ -12,6 +13,7 @@ use std::sync::Arc;
 use crate::MockGetter;
 #[cfg(debug_assertions)]
 use crate::Mocks;
+use crate::{BoxFuture, Fallback, MockOutput};
 
 /// A unique id for an object
 pub type MryId = u16;
@@ -46,40 +48,242 @@ impl Mry {
         self
     }
 
+    /// Records all calls and calls the real implementation unless a rule matches
+    pub fn spy(&mut self) -> &mut Self {
+        self.fallback(Fallback::RealImpl)
+    }
+
+    /// Sets what the mocks of the object do for calls no rule matches
+    #[cfg(debug_assertions)]
+    pub fn fallback(&mut self, fallback: Fallback) -> &mut Self {
+        self.generate().mocks.as_ref().unwrap().write().fallback = Some(fallback);
+        self
+    }
+
+    #[cfg(not(debug_assertions))]
+    pub fn fallback(&mut self, _fallback: Fallback) -> &mut Self {
+        self
+    }
+
+    /// Reports the rules of the object which never match when the object is dropped
+    #[cfg(debug_assertions)]
+    pub fn strict(&mut self) -> &mut Self {
+        self.generate().mocks.as_ref().unwrap().write().strict = true;
+        self
+    }
+
+    #[cfg(not(debug_assertions))]
+    pub fn strict(&mut self) -> &mut Self {
+        self
+    }
+
+    /// Removes the rules and logs of all methods, keeping the fallback of the object
+    #[cfg(debug_assertions)]
+    pub fn reset_all(&mut self) -> &mut Self {
+        if let Some(mocks) = &self.mocks {
+            mocks.write().reset_all();
+        }
+        self
+    }
+
+    #[cfg(not(debug_assertions))]
+    pub fn reset_all(&mut self) -> &mut Self {
+        self
+    }
+
+    /// Removes the logs of all methods, keeping the rules
+    #[cfg(debug_assertions)]
+    pub fn clear_logs(&mut self) -> &mut Self {
+        if let Some(mocks) = &self.mocks {
+            mocks.write().clear_logs();
+        }
+        self
+    }
+
+    #[cfg(not(debug_assertions))]
+    pub fn clear_logs(&mut self) -> &mut Self {
+        self
+    }
+
     #[doc(hidden)]
     #[cfg(debug_assertions)]
     pub fn record_call_and_find_mock_output<
-        I: PartialEq + Debug + Clone + Send + Sync + 'static,
+        I: Debug + Clone + Send + Sync + 'static,
         O: Debug + Send + Sync + 'static,
     >(
         &self,
         key: TypeId,
         name: &'static str,
         input: I,
+        default: Option<fn() -> O>,
+        location: Option<&'static Location<'static>>,
     ) -> Option<O> {
         self.mocks.as_ref().and_then(|mocks| {
             mocks
                 .write()
-                .record_call_and_find_mock_output(key, name, input)
+                .record_call_and_find_mock_output(key, name, input, default, location)
         })
     }
 
     #[cfg(not(debug_assertions))]
     pub fn record_call_and_find_mock_output<
-        I: PartialEq + Debug + Clone + Send + Sync + 'static,
+        I: Debug + Clone + Send + Sync + 'static,
+        O: Debug + Send + Sync + 'static,
+    >(
+        &self,
+        _key: TypeId,
+        _name: &'static str,
+        _input: I,
+        _default: Option<fn() -> O>,
+        _location: Option<&'static Location<'static>>,
+    ) -> Option<O> {
+        None
+    }
+
+    #[doc(hidden)]
+    #[cfg(debug_assertions)]
+    pub fn record_call_and_find_mock_future<
+        I: Debug + Clone + Send + Sync + 'static,
+        O: Debug + Send + Sync + 'static,
+    >(
+        &self,
+        key: TypeId,
+        name: &'static str,
+        input: I,
+        default: Option<fn() -> O>,
+        location: Option<&'static Location<'static>>,
+    ) -> Option<BoxFuture<O>> {
+        self.mocks.as_ref().and_then(|mocks| {
+            mocks
+                .write()
+                .record_call_and_find_mock_future(key, name, input, default, location)
+        })
+    }
+
+    #[cfg(not(debug_assertions))]
+    pub fn record_call_and_find_mock_future<
+        I: Debug + Clone + Send + Sync + 'static,
         O: Debug + Send + Sync + 'static,
     >(
         &self,
         _key: TypeId,
         _name: &'static str,
         _input: I,
+        _default: Option<fn() -> O>,
+        _location: Option<&'static Location<'static>>,
+    ) -> Option<BoxFuture<O>> {
+        None
+    }
+
+    #[doc(hidden)]
+    #[cfg(debug_assertions)]
+    pub fn record_call_and_find_real_impl<
+        I: Debug + Clone + Send + Sync + 'static,
+        O: Debug + Send + Sync + 'static,
+    >(
+        &self,
+        key: TypeId,
+        name: &'static str,
+        input: I,
+        default: Option<fn() -> O>,
+        location: Option<&'static Location<'static>>,
+    ) -> MockOutput<I, O> {
+        match &self.mocks {
+            Some(mocks) => mocks
+                .write()
+                .record_call_and_find_real_impl(key, name, input, default, location),
+            None => MockOutput::real_impl(name),
+        }
+    }
+
+    #[cfg(not(debug_assertions))]
+    pub fn record_call_and_find_real_impl<
+        I: Debug + Clone + Send + Sync + 'static,
+        O: Debug + Send + Sync + 'static,
+    >(
+        &self,
+        _key: TypeId,
+        name: &'static str,
+        _input: I,
+        _default: Option<fn() -> O>,
+        _location: Option<&'static Location<'static>>,
+    ) -> MockOutput<I, O> {
+        MockOutput::real_impl(name)
+    }
+
+    #[doc(hidden)]
+    #[cfg(debug_assertions)]
+    pub fn record_call_and_find_mock_output_mut<
+        I: Debug + Clone + Send + Sync + 'static,
+        O: Debug + Send + Sync + 'static,
+    >(
+        &self,
+        key: TypeId,
+        name: &'static str,
+        input: &mut I,
+        default: Option<fn() -> O>,
+        location: Option<&'static Location<'static>>,
+    ) -> Option<O> {
+        self.mocks.as_ref().and_then(|mocks| {
+            mocks
+                .write()
+                .record_call_and_find_mock_output_mut(key, name, input, default, location)
+        })
+    }
+
+    #[cfg(not(debug_assertions))]
+    pub fn record_call_and_find_mock_output_mut<
+        I: Debug + Clone + Send + Sync + 'static,
+        O: Debug + Send + Sync + 'static,
+    >(
+        &self,
+        _key: TypeId,
+        _name: &'static str,
+        _input: &mut I,
+        _default: Option<fn() -> O>,
+        _location: Option<&'static Location<'static>>,
     ) -> Option<O> {
         None
     }
 
     #[doc(hidden)]
     #[cfg(debug_assertions)]
-    pub fn mocks_write<'a, I: Send + Sync + 'static, O: 'static>(
+    pub fn record_call_and_find_mock_future_mut<
+        I: Debug + Clone + Send + Sync + 'static,
+        O: Debug + Send + Sync + 'static,
+    >(
+        &self,
+        key: TypeId,
+        name: &'static str,
+        input: &mut I,
+        default: Option<fn() -> O>,
+        location: Option<&'static Location<'static>>,
+    ) -> Option<BoxFuture<O>> {
+        self.mocks.as_ref().and_then(|mocks| {
+            mocks
+                .write()
+                .record_call_and_find_mock_future_mut(key, name, input, default, location)
+        })
+    }
+
+    #[cfg(not(debug_assertions))]
+    pub fn record_call_and_find_mock_future_mut<
+        I: Debug + Clone + Send + Sync + 'static,
+        O: Debug + Send + Sync + 'static,
+    >(
+        &self,
+        _key: TypeId,
+        _name: &'static str,
+        _input: &mut I,
+        _default: Option<fn() -> O>,
+        _location: Option<&'static Location<'static>>,
+    ) -> Option<BoxFuture<O>> {
+        None
+    }
+
+    #[doc(hidden)]
+    #[cfg(debug_assertions)]
+    pub fn mocks_write<'a, I: Debug + Send + Sync + 'static, O: Send + Sync + 'static>(
         &'a mut self,
     ) -> Box<dyn MockGetter<I, O> + 'a> {
         Box::new(self.generate().mocks.as_ref().unwrap().write())
@@ -219,7 +423,13 @@ mod test {
         let mry = Mry::default();
 
         assert_eq!(
-            mry.record_call_and_find_mock_output::<u8, u16>(TypeId::of::<usize>(), "name", 1u8),
+            mry.record_call_and_find_mock_output::<u8, u16>(
+                TypeId::of::<usize>(),
+                "name",
+                1u8,
+                None,
+                None
+            ),
             None
         );
     }
@@ -230,11 +440,97 @@ mod test {
 
         mry.mocks_write()
             .get_mut_or_create(TypeId::of::<usize>(), "name")
-            .returns(Matcher::Eq(1u8), 1u8);
+            .returns(Matcher::eq(1u8), 1u8);
 
         assert_eq!(
-            mry.record_call_and_find_mock_output::<u8, u8>(TypeId::of::<usize>(), "name", 1u8),
+            mry.record_call_and_find_mock_output::<u8, u8>(
+                TypeId::of::<usize>(),
+                "name",
+                1u8,
+                None,
+                None
+            ),
             Some(1u8)
         );
     }
+
+    #[test]
+    fn spy_applies_to_existing_mocks() {
+        let mut mry = Mry::default();
+
+        mry.mocks_write::<u8, u8>()
+            .get_mut_or_create(TypeId::of::<usize>(), "name")
+            .returns(Matcher::eq(1u8), 1u8);
+        mry.spy();
+
+        assert_eq!(
+            mry.record_call_and_find_mock_output::<u8, u8>(
+                TypeId::of::<usize>(),
+                "name",
+                2u8,
+                None,
+                None
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn fallback_nice_returns_default() {
+        let mut mry = Mry::default();
+        mry.fallback(Fallback::Nice);
+
+        assert_eq!(
+            mry.record_call_and_find_mock_output::<u8, u8>(
+                TypeId::of::<usize>(),
+                "name",
+                2u8,
+                Some(u8::default),
+                None
+            ),
+            Some(0u8)
+        );
+    }
+
+    #[test]
+    fn reset_all_keeps_fallback() {
+        let mut mry = Mry::default();
+        mry.spy();
+        mry.mocks_write::<u8, u8>()
+            .get_mut_or_create(TypeId::of::<usize>(), "name")
+            .returns(Matcher::eq(1u8), 1u8);
+        mry.reset_all();
+
+        assert_eq!(
+            mry.record_call_and_find_mock_output::<u8, u8>(
+                TypeId::of::<usize>(),
+                "name",
+                1u8,
+                None,
+                None
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn clear_logs_keeps_rules() {
+        let mut mry = Mry::default();
+        mry.mocks_write::<u8, u8>()
+            .get_mut_or_create(TypeId::of::<usize>(), "name")
+            .returns(Matcher::Any, 1u8);
+        mry.record_call_and_find_mock_output::<u8, u8>(
+            TypeId::of::<usize>(),
+            "name",
+            1u8,
+            None,
+            None,
+        );
+        mry.clear_logs();
+
+        let mut mocks = mry.mocks_write::<u8, u8>();
+        let mock = mocks.get_mut_or_create(TypeId::of::<usize>(), "name");
+        mock.assert_called(Matcher::Any, 0.into());
+        assert_eq!(mock.record_call_and_find_mock_output(1u8), Some(1u8));
+    }
 }
@@ -1,47 +1,158 @@
 use std::fmt::Debug;
+use std::future::Future;
+use std::iter::Peekable;
+use std::pin::Pin;
+use std::sync::Arc;
 
-use parking_lot::RwLock;
+use parking_lot::{Mutex, RwLock};
 
-#[derive(Debug, PartialEq)]
-pub(crate) enum Output<O> {
+/// Future returned by async behaviors
+pub type BoxFuture<O> = Pin<Box<dyn Future<Output = O> + Send + 'static>>;
+
+/// Real implementation passed to `wraps_real_impl`
+pub type RealImpl<'a, I, O> = Box<dyn FnOnce(I) -> O + 'a>;
+
+/// Function wrapping a real implementation.
+/// This is shared to be called outside of the lock of mocks, so the real implementation can call other mocks.
+pub type WrapsRealImplFn<I, O> =
+    Arc<Mutex<Box<dyn for<'a> FnMut(I, RealImpl<'a, I, O>) -> O + Send + 'static>>>;
+
+/// Values returned by `Behavior::Const`, which are peeked to describe the behavior without consuming them
+pub type Values<O> = Peekable<Box<dyn Iterator<Item = O> + Send + Sync + 'static>>;
+
+pub(crate) enum Output<I, O> {
     NotMatches,
+    Exhausted,
     CallsRealImpl,
+    CallsRealImplWith(I),
+    WrapsRealImpl(WrapsRealImplFn<I, O>),
     Found(O),
+    Pending(BoxFuture<O>),
+}
+
+impl<I: Debug, O: Debug> Debug for Output<I, O> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NotMatches => write!(f, "NotMatches"),
+            Self::Exhausted => write!(f, "Exhausted"),
+            Self::CallsRealImpl => write!(f, "CallsRealImpl"),
+            Self::CallsRealImplWith(input) => {
+                f.debug_tuple("CallsRealImplWith").field(input).finish()
+            }
+            Self::WrapsRealImpl(_) => write!(f, "WrapsRealImpl(_)"),
+            Self::Found(output) => f.debug_tuple("Found").field(output).finish(),
+            Self::Pending(_) => write!(f, "Pending(_)"),
+        }
+    }
+}
+
+#[cfg(test)]
+impl<I: PartialEq, O: PartialEq> PartialEq for Output<I, O> {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::NotMatches, Self::NotMatches)
+            | (Self::Exhausted, Self::Exhausted)
+            | (Self::CallsRealImpl, Self::CallsRealImpl) => true,
+            (Self::CallsRealImplWith(a), Self::CallsRealImplWith(b)) => a == b,
+            (Self::Found(a), Self::Found(b)) => a == b,
+            _ => false,
+        }
+    }
 }
 
 /// Behavior of mock
 pub enum Behavior<I, O> {
     /// Behaves with a function
     Function(Box<dyn FnMut(I) -> O + Send + Sync + 'static>),
-    /// Returns a constant value
-    Const(RwLock<Box<dyn Iterator<Item = O> + Send + Sync + 'static>>),
+    /// Behaves with a function that can modify the arguments
+    FunctionMut(Box<dyn FnMut(&mut I) -> O + Send + Sync + 'static>),
+    /// Returns values of an iterator
+    Const(RwLock<Values<O>>),
     /// Calls real implementation instead of mock
     CallsRealImpl,
+    /// Calls real implementation with the arguments returned by a function
+    CallsRealImplWith(Box<dyn FnMut(I) -> I + Send + Sync + 'static>),
+    /// Calls a function with the arguments and the real implementation
+    WrapsRealImpl(WrapsRealImplFn<I, O>),
+    /// Returns the output of a future awaited in the caller's future
+    Async(Box<dyn FnMut(I) -> BoxFuture<O> + Send + Sync + 'static>),
+    /// Panics with the message as the payload
+    Panics(String),
+    /// Panics with the message returned by a function as the payload
+    PanicsWith(Box<dyn FnMut(I) -> String + Send + Sync + 'static>),
 }
 
 impl<I: Debug, O: Debug> std::fmt::Debug for Behavior<I, O> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Self::Function(_) => f.debug_tuple("Function(_)").finish(),
-            Self::Const(cons) => f
-                .debug_tuple("Const")
-                .field(&cons.write().next().unwrap())
-                .finish(),
+            Self::FunctionMut(_) => write!(f, "FunctionMut(_)"),
+            Self::Const(cons) => match cons.write().peek() {
+                Some(value) => f.debug_tuple("Const").field(&value).finish(),
+                None => write!(f, "Const(<exhausted>)"),
+            },
             Self::CallsRealImpl => write!(f, "CallsRealImpl"),
+            Self::CallsRealImplWith(_) => write!(f, "CallsRealImplWith(_)"),
+            Self::WrapsRealImpl(_) => write!(f, "WrapsRealImpl(_)"),
+            Self::Async(_) => write!(f, "Async(_)"),
+            Self::Panics(message) => f.debug_tuple("Panics").field(message).finish(),
+            Self::PanicsWith(_) => write!(f, "PanicsWith(_)"),
         }
     }
 }
 
 impl<I: Clone, O> Behavior<I, O> {
-    pub(crate) fn called(&mut self, input: &I) -> Output<O> {
+    pub(crate) fn called(&mut self, input: &mut I) -> Output<I, O> {
         match self {
             Behavior::Function(function) => Output::Found(function(input.clone())),
-            Behavior::Const(cons) => Output::Found(cons.get_mut().next().unwrap()),
+            Behavior::FunctionMut(function) => Output::Found(function(input)),
+            Behavior::Const(cons) => match cons.get_mut().next() {
+                Some(output) => Output::Found(output),
+                None => Output::Exhausted,
+            },
             Behavior::CallsRealImpl => Output::CallsRealImpl,
+            Behavior::CallsRealImplWith(function) => {
+                Output::CallsRealImplWith(function(input.clone()))
+            }
+            Behavior::WrapsRealImpl(function) => Output::WrapsRealImpl(function.clone()),
+            Behavior::Async(function) => Output::Pending(function(input.clone())),
+            Behavior::Panics(message) => std::panic::panic_any(message.clone()),
+            Behavior::PanicsWith(function) => std::panic::panic_any(function(input.clone())),
         }
     }
 }
 
+impl<I, O> Behavior<I, O> {
+    /// Returns the values of the iterator in order.
+    pub(crate) fn values<T>(iter: T) -> Self
+    where
+        T: Iterator<Item = O> + Send + Sync + 'static,
+    {
+        let iter: Box<dyn Iterator<Item = O> + Send + Sync + 'static> = Box::new(iter);
+        Behavior::Const(RwLock::new(iter.peekable()))
+    }
+
+    /// Whether the behavior keeps returning values without running out.
+    /// Values of an iterator are not, because the end of an iterator is unknown until it is reached.
+    pub(crate) fn is_unlimited(&self) -> bool {
+        !matches!(self, Behavior::Const(_))
+    }
+
+    /// Whether the behavior has run out of values, checked before a call is counted.
+    pub(crate) fn is_exhausted(&mut self) -> bool {
+        match self {
+            Behavior::Const(cons) => cons.get_mut().peek().is_none(),
+            _ => false,
+        }
+    }
+}
+
+/// Relates a behavior type to the one receiving mutable references to the arguments
+#[doc(hidden)]
+pub trait WithMut {
+    type Mut;
+}
+
 mry_macros::create_behaviors!();
 
 #[cfg(test)]
@@ -53,27 +164,91 @@ mod tests {
     #[test]
     fn function() {
         assert_eq!(
-            Behavior::Function(Box::new(|()| "aaa")).called(&()),
+            Behavior::Function(Box::new(|()| "aaa")).called(&mut ()),
             Output::Found("aaa")
         );
     }
 
+    #[test]
+    fn function_mut() {
+        let mut input = vec![1];
+        let output = Behavior::FunctionMut(Box::new(|a: &mut Vec<u8>| {
+            a.push(2);
+            a.len()
+        }))
+        .called(&mut input);
+
+        assert_eq!(output, Output::Found(2));
+        assert_eq!(input, vec![1, 2]);
+    }
+
     #[test]
     fn const_value() {
         assert_eq!(
-            Behavior::Const(RwLock::new(Box::new(repeat("aaa")))).called(&()),
+            Behavior::values(repeat("aaa")).called(&mut ()),
             Output::Found("aaa")
         );
     }
 
+    #[test]
+    fn const_exhausted() {
+        let mut behavior = Behavior::values(vec!["aaa"].into_iter());
+        assert_eq!(behavior.called(&mut ()), Output::Found("aaa"));
+        assert_eq!(behavior.called(&mut ()), Output::Exhausted);
+    }
+
     #[test]
     fn calls_real_impl() {
         assert_eq!(
-            Behavior::<_, ()>::CallsRealImpl.called(&()),
+            Behavior::<_, ()>::CallsRealImpl.called(&mut ()),
             Output::CallsRealImpl
         );
     }
 
+    #[test]
+    fn calls_real_impl_with() {
+        assert_eq!(
+            Behavior::<_, ()>::CallsRealImplWith(Box::new(|a: u8| a * 2)).called(&mut 3),
+            Output::CallsRealImplWith(6)
+        );
+    }
+
+    #[test]
+    fn async_function() {
+        let output =
+            Behavior::Async(Box::new(|a: u8| Box::pin(async move { a + 1 }))).called(&mut 1);
+
+        match output {
+            Output::Pending(future) => assert_eq!(async_std::task::block_on(future), 2),
+            output => panic!("unexpected output: {:?}", output),
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "disk full")]
+    fn panics() {
+        Behavior::<_, ()>::Panics("disk full".into()).called(&mut ());
+    }
+
+    #[test]
+    fn panics_with_payload() {
+        let payload = std::panic::catch_unwind(|| {
+            Behavior::<_, ()>::PanicsWith(Box::new(|a: u8| format!("disk {} full", a)))
+                .called(&mut 3);
+        })
+        .unwrap_err();
+
+        assert_eq!(payload.downcast_ref::<String>().unwrap(), "disk 3 full");
+    }
+
+    #[test]
+    fn debug_panics() {
+        assert_eq!(
+            format!("{:?}", Behavior::<u8, u8>::Panics("disk full".into())),
+            r#"Panics("disk full")"#.to_string()
+        )
+    }
+
     #[test]
     fn debug_calls_real_impl() {
         assert_eq!(
@@ -85,14 +260,28 @@ mod tests {
     #[test]
     fn debug_const() {
         assert_eq!(
-            format!(
-                "{:?}",
-                Behavior::<u8, u8>::Const(RwLock::new(Box::new(repeat(3))))
-            ),
+            format!("{:?}", Behavior::<u8, u8>::values(repeat(3))),
             "Const(3)".to_string()
         )
     }
 
+    #[test]
+    fn debug_const_keeps_values() {
+        let mut behavior = Behavior::<(), u8>::values(vec![1, 2].into_iter());
+        assert_eq!(format!("{:?}", behavior), "Const(1)".to_string());
+        assert_eq!(behavior.called(&mut ()), Output::Found(1));
+        assert_eq!(format!("{:?}", behavior), "Const(2)".to_string());
+        assert_eq!(behavior.called(&mut ()), Output::Found(2));
+    }
+
+    #[test]
+    fn debug_const_exhausted() {
+        assert_eq!(
+            format!("{:?}", Behavior::<u8, u8>::values(std::iter::empty())),
+            "Const(<exhausted>)".to_string()
+        )
+    }
+
     #[test]
     fn debug_function() {
         assert_eq!(
@@ -100,4 +289,20 @@ mod tests {
             "Function(_)".to_string()
         )
     }
+
+    #[test]
+    fn is_unlimited() {
+        assert!(!Behavior::<(), u8>::values(repeat(3)).is_unlimited());
+        assert!(!Behavior::<(), u8>::values(std::iter::from_fn(|| None)).is_unlimited());
+        assert!(Behavior::<(), u8>::CallsRealImpl.is_unlimited());
+    }
+
+    #[test]
+    fn is_exhausted() {
+        let mut behavior = Behavior::<(), u8>::values(vec![3].into_iter());
+        assert!(!behavior.is_exhausted());
+        behavior.called(&mut ());
+        assert!(behavior.is_exhausted());
+        assert!(!Behavior::<(), u8>::CallsRealImpl.is_exhausted());
+    }
 }
@@ -1,47 +1,161 @@
 use std::fmt::Debug;
 
-#[derive(Debug)]
 /// An enum shows what arguments are expected
 pub enum Matcher<I> {
     /// Any value
     Any,
     #[doc(hidden)]
     Never,
-    /// Equal to the value
-    Eq(I),
     /// Composite matcher
     Composite(Box<dyn CompositeMatcher<I> + Send + Sync>),
 }
 
-#[doc(hidden)]
+/// A custom matcher used via `Matcher::Composite`.
+/// Its `Debug` representation is used as its description in failure messages.
 pub trait CompositeMatcher<I>: Debug {
+    /// Returns `true` if the input is matched
     fn matches(&self, input: &I) -> bool;
+
+    /// Called with an input served by the rule of the matcher, but not by assertions.
+    fn capture(&self, _input: &I) {}
+
+    /// Returns `true` if the matcher matches any input like `Any`, used to warn about shadowed rules.
+    fn matches_any(&self) -> bool {
+        false
+    }
+
+    /// Returns `true` if the description identifies the matcher, like `Eq` and tuples of them.
+    /// Only such matchers are compared by `replace`.
+    fn is_comparable(&self) -> bool {
+        false
+    }
+
+    /// Returns whether each argument is matched, for matchers of multiple arguments.
+    /// This is used to show which arguments don't match in failure messages.
+    fn match_args(&self, _input: &I) -> Option<Vec<ArgMatch>> {
+        None
+    }
+}
+
+/// Whether an argument is matched, with the argument and the matcher rendered by `Debug`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArgMatch {
+    pub matched: bool,
+    pub input: String,
+    pub matcher: String,
+}
+
+impl ArgMatch {
+    pub fn new<T: Debug>(matcher: &Matcher<T>, input: &T) -> Self {
+        Self {
+            matched: matcher.matches(input),
+            input: format!("{:?}", input),
+            matcher: format!("{:?}", matcher),
+        }
+    }
 }
 
-impl<I: PartialEq> Matcher<I> {
+impl<I> Matcher<I> {
     pub(crate) fn matches(&self, input: &I) -> bool {
         match self {
             Matcher::Any => true,
             Matcher::Never => false,
-            Matcher::Eq(value) => value == input,
             Matcher::Composite(matcher) => matcher.matches(input),
         }
     }
+
+    pub(crate) fn capture(&self, input: &I) {
+        if let Matcher::Composite(matcher) = self {
+            matcher.capture(input);
+        }
+    }
+
+    pub(crate) fn matches_any(&self) -> bool {
+        match self {
+            Matcher::Any => true,
+            Matcher::Never => false,
+            Matcher::Composite(matcher) => matcher.matches_any(),
+        }
+    }
+
+    pub(crate) fn is_comparable(&self) -> bool {
+        match self {
+            Matcher::Any | Matcher::Never => true,
+            Matcher::Composite(matcher) => matcher.is_comparable(),
+        }
+    }
+}
+
+impl<I: Debug> Matcher<I> {
+    /// Whether the matchers are described the same.
+    /// Matchers not identified by their description, like captors and predicates, are never equivalent.
+    pub(crate) fn is_equivalent(&self, other: &Matcher<I>) -> bool {
+        self.is_comparable()
+            && other.is_comparable()
+            && format!("{:?}", self) == format!("{:?}", other)
+    }
+
+    /// Matches each argument, or the whole input if the matcher is not for multiple arguments.
+    pub(crate) fn match_args(&self, input: &I) -> Vec<ArgMatch> {
+        match self {
+            Matcher::Composite(matcher) => matcher.match_args(input),
+            _ => None,
+        }
+        .unwrap_or_else(|| vec![ArgMatch::new(self, input)])
+    }
+}
+
+impl<I: PartialEq + Debug + Send + Sync + 'static> Matcher<I> {
+    /// Equal to the value.
+    pub fn eq(value: I) -> Self {
+        crate::matchers::eq(value)
+    }
+
+    /// Equal to the value.
+    /// This was an enum variant until 0.2, and is an alias of `Matcher::eq` now.
+    #[deprecated(since = "0.3.0", note = "use `Matcher::eq` instead")]
+    #[allow(non_snake_case)]
+    pub fn Eq(value: I) -> Self {
+        Self::eq(value)
+    }
 }
 
-impl<T: PartialEq> From<T> for Matcher<T> {
+impl<I: Debug + Send + Sync + 'static> Matcher<I> {
+    /// Matches a value matched by both of the matchers.
+    pub fn and(self, other: impl Into<Matcher<I>>) -> Matcher<I> {
+        crate::matchers::all_of(vec![self, other.into()])
+    }
+
+    /// Matches a value matched by either of the matchers.
+    pub fn or(self, other: impl Into<Matcher<I>>) -> Matcher<I> {
+        crate::matchers::any_of(vec![self, other.into()])
+    }
+}
+
+impl<I: Debug> Debug for Matcher<I> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Matcher::Any => write!(f, "Any"),
+            Matcher::Never => write!(f, "Never"),
+            // Composite matchers describe themselves
+            Matcher::Composite(matcher) => matcher.fmt(f),
+        }
+    }
+}
+
+impl<T: PartialEq + Debug + Send + Sync + 'static> From<T> for Matcher<T> {
     fn from(from: T) -> Self {
-        Self::Eq(from)
+        Self::eq(from)
     }
 }
 
 impl From<&str> for Matcher<String> {
     fn from(from: &str) -> Self {
-        Matcher::Eq(from.to_string())
+        Matcher::eq(from.to_string())
     }
 }
 
-impl<I: PartialEq> Into<Matcher<I>> for (Matcher<I>,) {
+impl<I> Into<Matcher<I>> for (Matcher<I>,) {
     fn into(self) -> Matcher<I> {
         self.0
     }
@@ -57,7 +171,7 @@ mod tests {
     fn from_str() {
         assert_eq!(
             format!("{:?}", Matcher::<String>::from("A")),
-            format!("{:?}", Matcher::Eq("A".to_string()))
+            format!("{:?}", Matcher::eq("A".to_string()))
         );
     }
 
@@ -65,16 +179,106 @@ mod tests {
     fn to_owned() {
         assert_eq!(
             format!("{:?}", Matcher::<String>::from("A")),
-            format!("{:?}", Matcher::Eq("A".to_string()))
+            format!("{:?}", Matcher::eq("A".to_string()))
         );
     }
 
+    #[test]
+    #[allow(deprecated)]
+    fn eq_alias() {
+        let matcher = Matcher::Eq(3);
+        assert!(matcher.matches(&3));
+        assert!(!matcher.matches(&4));
+        assert_eq!(format!("{:?}", matcher), "Eq(3)");
+    }
+
+    #[test]
+    fn and() {
+        let matcher = Matcher::from(3).and(crate::matchers::gt(2));
+        assert!(matcher.matches(&3));
+        assert!(!matcher.matches(&4));
+    }
+
+    #[test]
+    fn or() {
+        let matcher = Matcher::from(1).or(2);
+        assert!(matcher.matches(&1));
+        assert!(matcher.matches(&2));
+        assert!(!matcher.matches(&3));
+    }
+
+    #[test]
+    fn debug_two_values() {
+        let matcher: Matcher<(u8, u16)> = (Matcher::eq(3u8), Matcher::Any).into();
+        assert_eq!(format!("{:?}", matcher), "(Eq(3), Any)");
+    }
+
     #[test]
     fn matcher_two_values() {
-        let matcher: Matcher<(u8, u16)> = (Matcher::Eq(3u8), Matcher::Eq(2u16)).into();
+        let matcher: Matcher<(u8, u16)> = (Matcher::eq(3u8), Matcher::eq(2u16)).into();
         assert!(matcher.matches(&(3, 2)));
         assert!(!matcher.matches(&(3, 1)));
         assert!(!matcher.matches(&(1, 2)));
         assert!(!matcher.matches(&(1, 1)));
     }
+
+    #[test]
+    fn matches_any() {
+        assert!(Matcher::<u8>::Any.matches_any());
+        assert!(!Matcher::from(3).matches_any());
+        let matcher: Matcher<(u8, u16)> = (Matcher::Any, Matcher::Any).into();
+        assert!(matcher.matches_any());
+        let matcher: Matcher<(u8, u16)> = (Matcher::Any, Matcher::eq(2u16)).into();
+        assert!(!matcher.matches_any());
+    }
+
+    #[test]
+    fn match_args_single() {
+        assert_eq!(
+            Matcher::from(3).match_args(&4),
+            vec![ArgMatch {
+                matched: false,
+                input: "4".into(),
+                matcher: "Eq(3)".into(),
+            }]
+        );
+    }
+
+    #[test]
+    fn match_args_multiple() {
+        let matcher: Matcher<(u8, String)> = (Matcher::eq(3u8), Matcher::Any).into();
+        let matches: Vec<_> = matcher
+            .match_args(&(4, "a".into()))
+            .into_iter()
+            .map(|arg| (arg.matched, arg.input, arg.matcher))
+            .collect();
+        assert_eq!(
+            matches,
+            vec![
+                (false, "4".into(), "Eq(3)".into()),
+                (true, "\"a\"".into(), "Any".into())
+            ]
+        );
+    }
+
+    #[test]
+    fn is_equivalent() {
+        assert!(Matcher::from(3).is_equivalent(&Matcher::eq(3)));
+        assert!(!Matcher::from(3).is_equivalent(&Matcher::eq(4)));
+        assert!(!Matcher::from(3).is_equivalent(&Matcher::Any));
+        let matcher: Matcher<(u8, u16)> = (Matcher::eq(3u8), Matcher::Any).into();
+        let same: Matcher<(u8, u16)> = (Matcher::eq(3u8), Matcher::Any).into();
+        assert!(matcher.is_equivalent(&same));
+    }
+
+    #[test]
+    fn captors_and_predicates_are_not_equivalent() {
+        let captor = crate::Captor::<u8>::new();
+        assert!(!captor
+            .matcher()
+            .is_equivalent(&crate::Captor::new().matcher()));
+        assert!(!captor.matcher().is_equivalent(&captor.matcher()));
+        assert!(!crate::pred_named("small", |n: &u8| *n < 3)
+            .is_equivalent(&crate::pred_named("small", |n: &u8| *n < 5)));
+    }
 }
@@ -1,46 +1,243 @@
 mod behavior;
 mod matcher;
+mod order;
 
 pub use behavior::*;
 pub use matcher::*;
+pub use order::*;
+
+use std::fmt::Debug;
+use std::panic::Location;
+
+use crate::strict::is_strict;
+use crate::times::Times;
 
-#[derive(Debug)]
 pub(crate) struct Rule<I, O> {
     pub matcher: Matcher<I>,
     pub behavior: Behavior<I, O>,
+    pub limit: Option<usize>,
+    pub expected: Option<Times>,
+    pub hits: usize,
+    /// Where the rule is added
+    pub location: Option<&'static Location<'static>>,
+    /// Whether the rule is reported if it never matches, which is decided when the rule is added
+    pub strict: bool,
+    /// Whether the values of the behavior repeat forever, which is only known for `returns`
+    pub repeats: bool,
+}
+
+/// Options of a rule set by `MockLocator` before the rule is added
+#[doc(hidden)]
+#[derive(Default, Clone, Copy)]
+pub struct RuleOptions {
+    pub(crate) limit: Option<usize>,
+    pub(crate) expected: Option<Times>,
+    pub(crate) location: Option<&'static Location<'static>>,
 }
 
-impl<I: PartialEq + Clone, O> Rule<I, O> {
-    pub fn called(&mut self, input: &I) -> Output<O> {
-        if self.matcher.matches(input) {
-            return self.behavior.called(input);
+impl<I, O> Rule<I, O> {
+    pub fn new(matcher: Matcher<I>, behavior: Behavior<I, O>) -> Self {
+        Self {
+            matcher,
+            behavior,
+            limit: None,
+            expected: None,
+            hits: 0,
+            location: None,
+            strict: is_strict(),
+            repeats: false,
+        }
+    }
+
+    pub fn apply(&mut self, options: RuleOptions) {
+        self.limit = options.limit;
+        self.expected = options.expected;
+        self.location = options.location;
+    }
+
+    /// Whether the rule has matched the expected times, or has no expectation.
+    pub fn is_expectation_met(&self) -> bool {
+        match &self.expected {
+            Some(expected) => expected.contains(&self.hits),
+            None => true,
+        }
+    }
+
+    /// Whether the rule matches any input forever, so later rules never match in `RuleOrder::FirstWins`.
+    pub fn shadows_later_rules(&self) -> bool {
+        self.limit.is_none()
+            && self.matcher.matches_any()
+            && (self.repeats || self.behavior.is_unlimited())
+    }
+
+    /// Whether the rule should be reported because it never matched.
+    /// Rules with an expectation are verified by the expectation instead.
+    pub fn is_unused(&self, strict: bool) -> bool {
+        (strict || self.strict) && self.hits == 0 && self.expected.is_none()
+    }
+
+    /// Whether the rule has matched as many times as its limit.
+    pub fn is_retired(&self) -> bool {
+        matches!(self.limit, Some(limit) if self.hits >= limit)
+    }
+}
+
+impl<I: Clone, O> Rule<I, O> {
+    /// Only calls the rule serves are counted and captured, not the ones it has run out of values for.
+    pub fn called(&mut self, input: &mut I) -> Output<I, O> {
+        if self.is_retired() || !self.matcher.matches(input) {
+            return Output::NotMatches;
+        }
+        if self.behavior.is_exhausted() {
+            return Output::Exhausted;
+        }
+        // Counted before the behavior, which may panic or write back to `&mut` arguments
+        self.matcher.capture(input);
+        self.hits += 1;
+        self.behavior.called(input)
+    }
+}
+
+impl<I: Debug, O: Debug> Debug for Rule<I, O> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut debug = f.debug_struct("Rule");
+        debug
+            .field("matcher", &self.matcher)
+            .field("behavior", &self.behavior);
+        if let Some(limit) = self.limit {
+            debug.field("limit", &limit);
+        }
+        if let Some(expected) = &self.expected {
+            debug.field("expected", &format_args!("{}", expected));
+        }
+        if self.limit.is_some() || self.expected.is_some() {
+            debug.field("hits", &self.hits);
+        }
+        if let Some(location) = self.location {
+            debug.field("location", &format_args!("{}", location));
         }
-        Output::NotMatches
+        debug.finish()
     }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::Behavior1;
+    use crate::{Behavior, Behavior1};
 
     #[test]
     fn called_returns_none() {
-        let mut rule: Rule<u8, u8> = Rule {
-            matcher: Matcher::Never,
-            behavior: Behavior1::from(|_| panic!("should not be called!")).into(),
-        };
+        let mut rule: Rule<u8, u8> = Rule::new(
+            Matcher::Never,
+            Behavior1::from(|_| panic!("should not be called!")).into(),
+        );
 
-        assert_eq!(rule.called(&1), Output::NotMatches);
+        assert_eq!(rule.called(&mut 1), Output::NotMatches);
     }
 
     #[test]
     fn called_returns_some() {
-        let mut rule: Rule<u8, u8> = Rule {
-            matcher: Matcher::Any,
-            behavior: Behavior1::from(|u| u + 1).into(),
-        };
+        let mut rule: Rule<u8, u8> = Rule::new(Matcher::Any, Behavior1::from(|u| u + 1).into());
+
+        assert_eq!(rule.called(&mut 2), Output::Found(3))
+    }
+
+    #[test]
+    fn exhausted_calls_are_not_counted() {
+        let mut rule: Rule<u8, u8> = Rule::new(Matcher::Any, Behavior::values(std::iter::once(1)));
+
+        assert_eq!(rule.called(&mut 2), Output::Found(1));
+        assert_eq!(rule.called(&mut 2), Output::Exhausted);
+        assert_eq!(rule.hits, 1);
+    }
+
+    #[test]
+    fn called_retires_after_limit() {
+        let mut rule: Rule<u8, u8> = Rule::new(Matcher::Any, Behavior1::from(|u| u + 1).into());
+        rule.apply(RuleOptions {
+            limit: Some(2),
+            ..Default::default()
+        });
+
+        assert_eq!(rule.called(&mut 2), Output::Found(3));
+        assert!(!rule.is_retired());
+        assert_eq!(rule.called(&mut 2), Output::Found(3));
+        assert!(rule.is_retired());
+        assert_eq!(rule.called(&mut 2), Output::NotMatches);
+    }
+
+    #[test]
+    fn debug_shows_limit_and_hits() {
+        let mut rule: Rule<u8, u8> = Rule::new(Matcher::Any, Behavior1::from(|u| u + 1).into());
+        rule.apply(RuleOptions {
+            limit: Some(2),
+            ..Default::default()
+        });
+        rule.called(&mut 2);
+
+        assert_eq!(
+            format!("{:?}", rule),
+            "Rule { matcher: Any, behavior: Function(_), limit: 2, hits: 1 }"
+        );
+    }
+
+    #[test]
+    fn debug_shows_location() {
+        let location = Location::caller();
+        let mut rule: Rule<u8, u8> = Rule::new(Matcher::Any, Behavior1::from(|u| u + 1).into());
+        rule.apply(RuleOptions {
+            location: Some(location),
+            ..Default::default()
+        });
+
+        assert_eq!(
+            format!("{:?}", rule),
+            format!(
+                "Rule {{ matcher: Any, behavior: Function(_), location: {} }}",
+                location
+            )
+        );
+    }
+
+    #[test]
+    fn shadows_later_rules() {
+        let rule: Rule<u8, u8> = Rule::new(Matcher::Any, Behavior1::from(|u| u + 1).into());
+        assert!(rule.shadows_later_rules());
+
+        let mut rule: Rule<u8, u8> = Rule::new(Matcher::Any, Behavior1::from(|u| u + 1).into());
+        rule.apply(RuleOptions {
+            limit: Some(2),
+            ..Default::default()
+        });
+        assert!(!rule.shadows_later_rules());
+
+        let rule: Rule<u8, u8> = Rule::new(Matcher::from(2), Behavior1::from(|u| u + 1).into());
+        assert!(!rule.shadows_later_rules());
+
+        let mut rule: Rule<u8, u8> =
+            Rule::new(Matcher::Any, Behavior::values(std::iter::repeat(1)));
+        assert!(!rule.shadows_later_rules());
+        rule.repeats = true;
+        assert!(rule.shadows_later_rules());
+    }
+
+    #[test]
+    fn expectation() {
+        let mut rule: Rule<u8, u8> = Rule::new(Matcher::Any, Behavior1::from(|u| u + 1).into());
+        rule.apply(RuleOptions {
+            expected: Some((1..=2).into()),
+            ..Default::default()
+        });
 
-        assert_eq!(rule.called(&2), Output::Found(3))
+        assert!(!rule.is_expectation_met());
+        rule.called(&mut 2);
+        assert!(rule.is_expectation_met());
+        assert_eq!(
+            format!("{:?}", rule),
+            "Rule { matcher: Any, behavior: Function(_), expected: 1..=2, hits: 1 }"
+        );
+        rule.called(&mut 2);
+        rule.called(&mut 2);
+        assert!(!rule.is_expectation_met());
     }
 }
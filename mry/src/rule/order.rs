@@ -0,0 +1,56 @@
+use std::cell::Cell;
+use std::marker::PhantomData;
+
+/// Which of the rules matching a call is used
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RuleOrder {
+    /// The rule added first is used. This is the default.
+    FirstWins,
+    /// The rule added last is used, so a test can override rules added in a shared setup.
+    LastWins,
+}
+
+thread_local! {
+    static DEFAULT_RULE_ORDER: Cell<Option<RuleOrder>> = const { Cell::new(None) };
+}
+
+/// Sets the order of rules of mocks without one in the current thread until the guard is dropped.
+pub fn default_rule_order(order: RuleOrder) -> RuleOrderGuard {
+    RuleOrderGuard {
+        previous: DEFAULT_RULE_ORDER.with(|cell| cell.replace(Some(order))),
+        _not_send: PhantomData,
+    }
+}
+
+pub(crate) fn current_default_rule_order() -> Option<RuleOrder> {
+    DEFAULT_RULE_ORDER.with(|cell| cell.get())
+}
+
+/// Guard returned by `default_rule_order`, which restores the previous order on drop
+#[must_use = "the order is restored when the guard is dropped"]
+pub struct RuleOrderGuard {
+    previous: Option<RuleOrder>,
+    // The order is set for the current thread
+    _not_send: PhantomData<*const ()>,
+}
+
+impl Drop for RuleOrderGuard {
+    fn drop(&mut self) {
+        DEFAULT_RULE_ORDER.with(|cell| cell.set(self.previous));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn default_rule_order_is_restored() {
+        assert_eq!(current_default_rule_order(), None);
+        {
+            let _guard = default_rule_order(RuleOrder::LastWins);
+            assert_eq!(current_default_rule_order(), Some(RuleOrder::LastWins));
+        }
+        assert_eq!(current_default_rule_order(), None);
+    }
+}
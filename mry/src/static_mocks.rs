@@ -1,9 +1,10 @@
-use crate::{mock::Mock, MockGetter, Mocks};
-use async_recursion::async_recursion;
+use crate::mocks::report;
+use crate::{mock::Mock, BoxFuture, MockGetter, MockOutput, Mocks};
 use once_cell::sync::Lazy;
 use parking_lot::{Mutex, RwLock};
 use std::{
-    any::TypeId, collections::HashMap, fmt::Debug, future::Future, ops::Deref, pin::Pin, sync::Arc,
+    any::TypeId, collections::HashMap, fmt::Debug, future::Future, ops::Deref, panic::Location,
+    pin::Pin, sync::Arc,
 };
 
 pub static STATIC_MOCKS: Lazy<RwLock<StaticMocks>> =
@@ -26,15 +27,56 @@ pub struct StaticMockLock<'a> {
     pub lock: Box<dyn Deref<Target = ()> + 'a>,
 }
 
-impl<'a> Drop for StaticMockLock<'a> {
+/// Locks of a test, which verify the mocks of all the locked functions at once when dropped
+#[doc(hidden)]
+pub struct StaticMockLocks<'a>(pub Vec<StaticMockLock<'a>>);
+
+impl<'a> StaticMockLocks<'a> {
+    fn acquire(mutexes: &'a [StaticMockMutex]) -> Self {
+        Self(
+            mutexes
+                .iter()
+                .map(|mutex| StaticMockLock {
+                    key: mutex.key,
+                    name: mutex.name.clone(),
+                    lock: Box::new(mutex.mutex.lock()),
+                })
+                .collect(),
+        )
+    }
+}
+
+impl<'a> Drop for StaticMockLocks<'a> {
     fn drop(&mut self) {
-        let lock = &mut STATIC_MOCKS.write().0;
-        if lock.remove(&self.key).is_none() {
-            panic!(
-                "{} is locked but no used. Remove {} from mry::lock",
-                self.name, self.name
-            );
-        };
+        let mut unused_locks = Vec::new();
+        let mut unmet = Vec::new();
+        let mut unused = Vec::new();
+        {
+            let mocks = &mut STATIC_MOCKS.write().0;
+            for lock in &self.0 {
+                match mocks.remove(&lock.key) {
+                    Some((mock_unmet, mock_unused)) => {
+                        unmet.extend(mock_unmet);
+                        unused.extend(mock_unused);
+                    }
+                    None => unused_locks.push(format!(
+                        "{} is locked but no used. Remove {} from mry::lock",
+                        lock.name, lock.name
+                    )),
+                }
+            }
+        }
+        // Skips the panic while unwinding to avoid aborting the test
+        if std::thread::panicking() {
+            return;
+        }
+        let sections: Vec<_> = unused_locks
+            .into_iter()
+            .chain(report(unmet, unused))
+            .collect();
+        if !sections.is_empty() {
+            panic!("{}", sections.join("\n"))
+        }
     }
 }
 
@@ -50,7 +92,7 @@ fn check_locked(key: &TypeId) -> bool {
         .unwrap_or(false)
 }
 
-impl<I: Send + Sync + 'static, O: 'static> MockGetter<I, O> for StaticMocks {
+impl<I: Debug + Send + Sync + 'static, O: Send + Sync + 'static> MockGetter<I, O> for StaticMocks {
     fn get(&self, key: &TypeId, name: &'static str) -> Option<&Mock<I, O>> {
         if !check_locked(key) {
             panic!("the lock of `{}` is not acquired. See `mry::lock`.", name);
@@ -68,16 +110,95 @@ impl<I: Send + Sync + 'static, O: 'static> MockGetter<I, O> for StaticMocks {
 
 impl StaticMocks {
     pub fn record_call_and_find_mock_output<
-        I: PartialEq + Debug + Clone + Send + Sync + 'static,
+        I: Debug + Clone + Send + Sync + 'static,
         O: Debug + Send + Sync + 'static,
     >(
         &mut self,
         key: TypeId,
         name: &'static str,
         input: I,
+        default: Option<fn() -> O>,
+        location: Option<&'static Location<'static>>,
     ) -> Option<O> {
         if check_locked(&key) {
-            self.0.record_call_and_find_mock_output(key, name, input)
+            self.0
+                .record_call_and_find_mock_output(key, name, input, default, location)
+        } else {
+            None
+        }
+    }
+
+    pub fn record_call_and_find_mock_future<
+        I: Debug + Clone + Send + Sync + 'static,
+        O: Debug + Send + Sync + 'static,
+    >(
+        &mut self,
+        key: TypeId,
+        name: &'static str,
+        input: I,
+        default: Option<fn() -> O>,
+        location: Option<&'static Location<'static>>,
+    ) -> Option<BoxFuture<O>> {
+        if check_locked(&key) {
+            self.0
+                .record_call_and_find_mock_future(key, name, input, default, location)
+        } else {
+            None
+        }
+    }
+
+    pub fn record_call_and_find_real_impl<
+        I: Debug + Clone + Send + Sync + 'static,
+        O: Debug + Send + Sync + 'static,
+    >(
+        &mut self,
+        key: TypeId,
+        name: &'static str,
+        input: I,
+        default: Option<fn() -> O>,
+        location: Option<&'static Location<'static>>,
+    ) -> MockOutput<I, O> {
+        if check_locked(&key) {
+            self.0
+                .record_call_and_find_real_impl(key, name, input, default, location)
+        } else {
+            MockOutput::real_impl(name)
+        }
+    }
+
+    pub fn record_call_and_find_mock_output_mut<
+        I: Debug + Clone + Send + Sync + 'static,
+        O: Debug + Send + Sync + 'static,
+    >(
+        &mut self,
+        key: TypeId,
+        name: &'static str,
+        input: &mut I,
+        default: Option<fn() -> O>,
+        location: Option<&'static Location<'static>>,
+    ) -> Option<O> {
+        if check_locked(&key) {
+            self.0
+                .record_call_and_find_mock_output_mut(key, name, input, default, location)
+        } else {
+            None
+        }
+    }
+
+    pub fn record_call_and_find_mock_future_mut<
+        I: Debug + Clone + Send + Sync + 'static,
+        O: Debug + Send + Sync + 'static,
+    >(
+        &mut self,
+        key: TypeId,
+        name: &'static str,
+        input: &mut I,
+        default: Option<fn() -> O>,
+        location: Option<&'static Location<'static>>,
+    ) -> Option<BoxFuture<O>> {
+        if check_locked(&key) {
+            self.0
+                .record_call_and_find_mock_future_mut(key, name, input, default, location)
         } else {
             None
         }
@@ -102,35 +223,18 @@ pub fn __mutexes(mut keys: Vec<(TypeId, String)>) -> Vec<StaticMockMutex> {
 }
 
 #[doc(hidden)]
-pub fn __lock_and_run<T>(mut mutexes: Vec<StaticMockMutex>, function: fn() -> T) -> T {
-    if let Some(mutex) = mutexes.pop() {
-        let _lock = StaticMockLock {
-            key: mutex.key,
-            name: mutex.name,
-            lock: Box::new(mutex.mutex.lock()),
-        };
-        __lock_and_run(mutexes, function)
-    } else {
-        function()
-    }
+pub fn __lock_and_run<T>(mutexes: Vec<StaticMockMutex>, function: fn() -> T) -> T {
+    let _locks = StaticMockLocks::acquire(&mutexes);
+    function()
 }
 
 #[doc(hidden)]
-#[async_recursion(?Send)]
 pub async fn __async_lock_and_run<T>(
-    mut mutexes: Vec<StaticMockMutex>,
+    mutexes: Vec<StaticMockMutex>,
     function: fn() -> Pin<Box<dyn Future<Output = T>>>,
 ) -> T {
-    if let Some(mutex) = mutexes.pop() {
-        let _lock = StaticMockLock {
-            key: mutex.key,
-            name: mutex.name,
-            lock: Box::new(mutex.mutex.lock()),
-        };
-        __async_lock_and_run(mutexes, function).await
-    } else {
-        function().await
-    }
+    let _locks = StaticMockLocks::acquire(&mutexes);
+    function().await
 }
 
 #[cfg(test)]
@@ -154,7 +258,9 @@ mod tests {
                 .record_call_and_find_mock_output::<(), ()>(
                     returns_none_if_not_mocked.type_id(),
                     "meow",
-                    ()
+                    (),
+                    None,
+                    None
                 ),
             None
         );
@@ -165,7 +271,7 @@ mod tests {
         let mut mocks = Mocks::default();
         mocks
             .get_mut_or_create(returns_some_if_mocked.type_id(), "meow")
-            .returns(Matcher::Eq(()), ());
+            .returns(Matcher::eq(()), ());
         let mut static_mocks = StaticMocks(mocks);
 
         let mutex = Arc::new(Mutex::default());
@@ -179,7 +285,9 @@ mod tests {
             static_mocks.record_call_and_find_mock_output::<(), ()>(
                 returns_some_if_mocked.type_id(),
                 "meow",
-                ()
+                (),
+                None,
+                None
             ),
             Some(())
         );
@@ -240,11 +348,11 @@ mod tests {
             Mock::<usize, usize>::new(""),
         );
 
-        drop(StaticMockLock {
+        drop(StaticMockLocks(vec![StaticMockLock {
             key: delete_mock_when_lock_is_dropped.type_id(),
             name: "name".to_string(),
             lock: Box::new(Box::new(())),
-        });
+        }]));
 
         assert!(MockGetter::<usize, usize>::get(
             &STATIC_MOCKS.read().0,
@@ -0,0 +1,59 @@
+use std::cell::Cell;
+use std::marker::PhantomData;
+
+/// Environment variable that makes all mocks strict if set to anything other than `0`
+const STRICT_ENV: &str = "MRY_STRICT";
+
+thread_local! {
+    static STRICT: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Reports rules added in the current thread until the guard is dropped, if they never match.
+/// They are reported when the object, or the lock of a static function, is dropped.
+pub fn strict() -> StrictGuard {
+    StrictGuard {
+        previous: STRICT.with(|cell| cell.replace(true)),
+        _not_send: PhantomData,
+    }
+}
+
+/// Whether rules added now are reported if they never match.
+pub(crate) fn is_strict() -> bool {
+    STRICT.with(|cell| cell.get())
+        || matches!(std::env::var_os(STRICT_ENV), Some(value) if value != "0")
+}
+
+/// Guard returned by `strict`, which restores the previous strictness on drop
+#[must_use = "the strictness is restored when the guard is dropped"]
+pub struct StrictGuard {
+    previous: bool,
+    // The strictness is set for the current thread
+    _not_send: PhantomData<*const ()>,
+}
+
+impl Drop for StrictGuard {
+    fn drop(&mut self) {
+        STRICT.with(|cell| cell.set(self.previous));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn strict_is_restored() {
+        let current = || STRICT.with(|cell| cell.get());
+        assert!(!current());
+        {
+            let _guard = strict();
+            assert!(current());
+            {
+                let _guard = strict();
+                assert!(current());
+            }
+            assert!(current());
+        }
+        assert!(!current());
+    }
+}
@@ -0,0 +1,95 @@
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+#[mry::mry]
+#[derive(Default)]
+struct Cat {}
+
+#[mry::mry]
+impl Cat {
+    fn meow(&self, base: String, count: usize) -> String {
+        base.repeat(count)
+    }
+}
+
+#[mry::mry]
+trait Store {
+    fn save(&self, key: String, value: usize) -> bool;
+}
+
+#[mry::mry]
+fn hello(base: String, count: usize) -> String {
+    base.repeat(count)
+}
+
+#[test]
+#[should_panic(expected = r#"Cat::meow was not called as expected
+matcher: (Eq("meow"), Eq(2))
+times: 1
+matched: 0
+calls:
+  #0 CatMeowCall { base: "meow", count: 3 }
+    ✓ base: "meow"
+    ✗ count: 3 doesn't match Eq(2)
+closest call #0:"#)]
+fn struct_method() {
+    let mut cat = mry::new!(Cat {});
+    cat.mock_meow(mry::Any, mry::Any).returns("mocked".into());
+
+    cat.meow("meow".into(), 3);
+
+    cat.mock_meow("meow", 2).assert_called(1);
+}
+
+#[test]
+#[should_panic(expected = r#"Store::save was not called as expected
+matcher: (Eq("key"), Any)
+times: 2
+matched: 1
+calls:
+  #0 StoreSaveCall { key: "key", value: 1 }
+    ✓ key: "key"
+    ✓ value: 1"#)]
+fn trait_method() {
+    let mut store = MockStore::default();
+    store.mock_save(mry::Any, mry::Any).returns(true);
+
+    store.save("key".into(), 1);
+
+    store.mock_save("key", mry::Any).assert_called(2);
+}
+
+#[test]
+#[mry::lock(hello)]
+#[should_panic(expected = r#"hello was not called as expected
+matcher: (Eq("hi"), Any)
+times: 1
+matched: 0
+calls: none"#)]
+fn static_function() {
+    mock_hello(mry::Any, mry::Any).returns("mocked".into());
+
+    mock_hello("hi", mry::Any).assert_called(1);
+}
+
+#[test]
+fn shows_where_rules_are_added_and_asserted() {
+    let mut cat = mry::new!(Cat {});
+    let added = line!() + 1;
+    cat.mock_meow(mry::Any, mry::Any).returns("mocked".into());
+
+    let mut asserted = 0;
+    let message = catch_unwind(AssertUnwindSafe(|| {
+        asserted = line!() + 1;
+        cat.mock_meow("meow", 2).assert_called(1);
+    }))
+    .unwrap_err()
+    .downcast::<String>()
+    .unwrap();
+
+    assert!(message.contains(&format!(
+        "rules:\n  #0 (Any, Any) => Const(\"mocked\"), added at {}:{}:",
+        file!(),
+        added
+    )));
+    assert!(message.contains(&format!("\nasserted at {}:{}:", file!(), asserted)));
+}
@@ -0,0 +1,108 @@
+use std::time::Duration;
+
+use async_std::future::timeout;
+
+#[mry::mry]
+#[derive(Default)]
+struct Cat {
+    name: String,
+}
+
+#[mry::mry]
+impl Cat {
+    async fn meow(&self, count: usize) -> String {
+        format!("{}: {}", self.name, "meow".repeat(count))
+    }
+
+    async fn purr(count: usize) -> String {
+        "purr".repeat(count)
+    }
+}
+
+#[mry::mry]
+#[async_trait::async_trait]
+pub trait Dog {
+    async fn bark(&self, count: usize) -> String;
+}
+
+#[mry::mry]
+async fn hello(count: usize) -> String {
+    "hello".repeat(count)
+}
+
+#[async_std::test]
+async fn returns_async() {
+    let mut cat = Cat::default();
+    cat.mock_meow(mry::Any).returns_async(|count| async move {
+        async_std::task::yield_now().await;
+        "meow".repeat(count)
+    });
+
+    assert_eq!(cat.meow(2).await, "meowmeow".to_string());
+}
+
+#[async_std::test]
+async fn returns_after() {
+    let mut cat = Cat::default();
+    cat.mock_meow(mry::Any)
+        .returns_after(Duration::from_millis(50), "late".to_string());
+
+    assert_eq!(cat.meow(2).await, "late".to_string());
+}
+
+#[async_std::test]
+async fn returns_after_times_out() {
+    let mut cat = Cat::default();
+    cat.mock_meow(mry::Any)
+        .returns_after(Duration::from_secs(10), "late".to_string());
+
+    assert!(timeout(Duration::from_millis(50), cat.meow(2))
+        .await
+        .is_err());
+    cat.mock_meow(mry::Any).assert_called(1);
+}
+
+#[async_std::test]
+async fn returns_after_in_trait() {
+    let mut dog = MockDog::default();
+    dog.mock_bark(mry::Any)
+        .returns_after(Duration::from_millis(10), "woof".to_string());
+
+    assert_eq!(dog.bark(1).await, "woof".to_string());
+}
+
+#[mry::lock(Cat::purr)]
+#[async_std::test]
+async fn returns_async_in_static_method() {
+    Cat::mock_purr(mry::Any).returns_async(|count| async move { count.to_string() });
+
+    assert_eq!(Cat::purr(2).await, "2".to_string());
+}
+
+#[mry::lock(hello)]
+#[async_std::test]
+async fn returns_after_in_function() {
+    mock_hello(mry::Any).returns_after(Duration::from_millis(10), "hi".to_string());
+
+    assert_eq!(hello(2).await, "hi".to_string());
+}
+
+#[mry::mry]
+#[derive(Default)]
+struct Clock {}
+
+#[mry::mry]
+impl Clock {
+    fn now(&self) -> u64 {
+        0
+    }
+}
+
+#[test]
+#[should_panic(expected = "Clock::now returns a future, but it is not an async method")]
+fn returns_async_to_sync_method() {
+    let mut clock = Clock::default();
+    clock.mock_now().returns_async(|()| async { 1 });
+
+    clock.now();
+}
@@ -0,0 +1,65 @@
+#[mry::mry]
+#[derive(Default)]
+struct Cat {
+    name: String,
+}
+
+#[mry::mry]
+impl Cat {
+    fn meow(&self, base: &str, count: usize) -> String {
+        format!("{}: {}", self.name, base.repeat(count))
+    }
+}
+
+#[mry::mry]
+trait Store {
+    fn save(&self, key: String, value: usize) -> bool;
+}
+
+#[mry::mry]
+fn hello(count: usize) -> String {
+    "hello".repeat(count)
+}
+
+#[test]
+fn named_fields() {
+    let mut cat = mry::new!(Cat {
+        name: "Tama".into()
+    });
+    cat.mock_meow(mry::Any, mry::Any).returns("mocked".into());
+
+    cat.meow("meow", 2);
+
+    let calls = cat.mock_meow("meow", mry::Any).assert_called(1);
+    assert_eq!(calls[0].base, "meow".to_string());
+    assert_eq!(calls[0].count, 2);
+    assert_eq!(
+        cat.mock_meow(mry::Any, 2).calls()[0].input.base,
+        "meow".to_string()
+    );
+}
+
+#[test]
+fn debug_shows_field_names() {
+    let mut store = MockStore::default();
+    store.mock_save(mry::Any, mry::Any).returns(true);
+
+    store.save("key".into(), 1);
+
+    let calls = store.mock_save(mry::Any, mry::Any).assert_called(1);
+    assert_eq!(
+        format!("{:?}", calls),
+        r#"[StoreSaveCall { key: "key", value: 1 }]"#
+    );
+}
+
+#[test]
+#[mry::lock(hello)]
+fn function() {
+    mock_hello(mry::Any).returns("mocked".into());
+
+    hello(3);
+
+    let calls: Vec<HelloCall> = mock_hello(mry::Any).assert_called(1);
+    assert_eq!(calls[0].count, 3);
+}
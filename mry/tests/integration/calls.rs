@@ -0,0 +1,115 @@
+use mry::{Fallback, ServedBy};
+
+#[mry::mry]
+#[derive(Default)]
+struct Cat {
+    name: String,
+}
+
+#[mry::mry]
+impl Cat {
+    fn meow(&self, count: usize) -> String {
+        format!("{}: {}", self.name, "meow".repeat(count))
+    }
+
+    fn greet(&self, base: &str) -> String {
+        format!("{} {}", base, self.name)
+    }
+
+    async fn purr(&self, count: usize) -> String {
+        "purr".repeat(count)
+    }
+}
+
+#[test]
+fn records_outputs_and_served_by() {
+    let mut cat = mry::new!(Cat {
+        name: "Tama".into()
+    });
+    cat.mock_meow(1).returns("mocked".into());
+    cat.mock_meow(2).calls_real_impl();
+    cat.mock_meow(mry::Any).fallback(Fallback::Nice);
+
+    cat.meow(1);
+    cat.meow(2);
+    cat.meow(3);
+
+    let calls = cat.mock_meow(mry::Any).calls();
+    let summary: Vec<_> = calls
+        .iter()
+        .map(|call| (call.input.count, call.output(), call.served_by))
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            (1, Some("\"mocked\""), ServedBy::Rule),
+            (2, Some("\"Tama: meowmeow\""), ServedBy::RealImpl),
+            (3, Some("\"\""), ServedBy::Fallback),
+        ]
+    );
+    assert!(calls[0].sequence < calls[1].sequence);
+    assert!(calls[0].timestamp <= calls[1].timestamp);
+    assert_eq!(calls[0].thread, std::thread::current().id());
+}
+
+#[test]
+fn filtered_by_matcher() {
+    let mut cat = mry::spy!(Cat {
+        name: "Tama".into()
+    });
+
+    cat.meow(1);
+    cat.meow(2);
+
+    let calls = cat.mock_meow(2).calls();
+    assert_eq!(calls.len(), 1);
+    assert_eq!(calls[0].output(), Some("\"Tama: meowmeow\""));
+    assert_eq!(calls[0].served_by, ServedBy::Fallback);
+}
+
+#[test]
+fn not_called() {
+    let mut cat = mry::new!(Cat {
+        name: "Tama".into()
+    });
+
+    assert!(cat.mock_meow(mry::Any).calls().is_empty());
+}
+
+#[test]
+fn real_impl_with_references_is_not_recorded() {
+    let mut cat = mry::spy!(Cat {
+        name: "Tama".into()
+    });
+
+    cat.greet("Hi");
+
+    assert_eq!(cat.mock_greet(mry::Any).calls()[0].output(), None);
+}
+
+#[async_std::test]
+async fn async_outputs() {
+    let mut cat = mry::new!(Cat {
+        name: "Tama".into()
+    });
+    cat.mock_purr(1)
+        .returns_async(|_| async { "async".to_string() });
+    cat.mock_purr(2).calls_real_impl();
+
+    cat.purr(1).await;
+    cat.purr(2).await;
+
+    let outputs: Vec<_> = cat
+        .mock_purr(mry::Any)
+        .calls()
+        .iter()
+        .map(|call| call.output().map(String::from))
+        .collect();
+    assert_eq!(
+        outputs,
+        vec![
+            Some("\"async\"".to_string()),
+            Some("\"purrpurr\"".to_string())
+        ]
+    );
+}
@@ -0,0 +1,67 @@
+use mry::Captor;
+
+#[mry::mry]
+#[derive(Default)]
+struct Cat {
+    name: String,
+}
+
+#[mry::mry]
+impl Cat {
+    fn meow(&self, base: String, count: usize) -> String {
+        format!("{}: {}", self.name, base.repeat(count))
+    }
+}
+
+#[test]
+fn captures_matched_arguments() {
+    let mut cat = mry::new!(Cat {
+        name: "Tama".into()
+    });
+    let base = Captor::<String>::new();
+    cat.mock_meow(base.matcher(), 2)
+        .returns("Called".to_string());
+    cat.mock_meow(mry::Any, mry::Any)
+        .returns("Other".to_string());
+
+    cat.meow("meow".into(), 2);
+    cat.meow("nya".into(), 3);
+    cat.meow("mew".into(), 2);
+
+    assert_eq!(base.all(), vec!["meow".to_string(), "mew".to_string()]);
+    assert_eq!(base.last(), Some("mew".to_string()));
+    assert_eq!(base.nth(0), Some("meow".to_string()));
+}
+
+#[test]
+fn captures_across_threads() {
+    let mut cat = mry::new!(Cat {
+        name: "Tama".into()
+    });
+    let count = Captor::new();
+    cat.mock_meow(mry::Any, count.matcher())
+        .returns("Called".to_string());
+
+    let handle = std::thread::spawn(move || {
+        cat.meow("meow".into(), 3);
+    });
+    handle.join().unwrap();
+
+    assert_eq!(count.all(), vec![3]);
+}
+
+#[test]
+fn assertions_do_not_capture() {
+    let mut cat = mry::new!(Cat {
+        name: "Tama".into()
+    });
+    let base = Captor::<String>::new();
+    cat.mock_meow(base.matcher(), 2)
+        .returns("Called".to_string());
+
+    cat.meow("meow".into(), 2);
+
+    cat.mock_meow(base.matcher(), 2).assert_called(1);
+    cat.mock_meow(base.matcher(), 2).calls();
+    assert_eq!(base.all().len(), 1);
+}
@@ -0,0 +1,150 @@
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+#[mry::mry]
+#[derive(Default)]
+struct Cat {
+    name: String,
+}
+
+#[mry::mry]
+impl Cat {
+    fn meow(&self, count: usize) -> String {
+        format!("{}: {}", self.name, "meow".repeat(count))
+    }
+
+    fn weight(&self) -> usize {
+        4
+    }
+}
+
+#[mry::mry]
+fn hello(count: usize) -> String {
+    "hello".repeat(count)
+}
+
+#[mry::mry]
+fn goodbye(count: usize) -> String {
+    "goodbye".repeat(count)
+}
+
+#[test]
+fn expectation_met() {
+    let mut cat = mry::new!(Cat {
+        name: "Tama".into()
+    });
+    cat.mock_meow(3).expect(1..=2).returns("mocked".into());
+
+    assert_eq!(cat.meow(3), "mocked".to_string());
+}
+
+#[test]
+#[should_panic(
+    expected = "unmet expectations:\nCat::meow was expected to be called 1..=2 times with Eq(3), but called 0 times"
+)]
+fn expectation_not_met() {
+    let mut cat = mry::new!(Cat {
+        name: "Tama".into()
+    });
+    cat.mock_meow(3).expect(1..=2).returns("mocked".into());
+}
+
+#[test]
+fn exhausted_rule_is_not_counted() {
+    let mut cat = mry::new!(Cat {
+        name: "Tama".into()
+    });
+    cat.mock_meow(3).expect(1).returns_once("once".into());
+    cat.mock_meow(mry::Any).returns("fallback".into());
+
+    assert_eq!(cat.meow(3), "once".to_string());
+    assert_eq!(cat.meow(3), "fallback".to_string());
+    assert_eq!(cat.meow(3), "fallback".to_string());
+}
+
+#[test]
+fn reports_all_unmet_expectations() {
+    let message = catch_unwind(AssertUnwindSafe(|| {
+        let mut cat = mry::new!(Cat {
+            name: "Tama".into()
+        });
+        cat.mock_meow(3).expect(1).returns("mocked".into());
+        cat.mock_weight().expect(0).returns(5);
+
+        cat.weight();
+    }))
+    .unwrap_err();
+
+    let message = message.downcast_ref::<String>().unwrap();
+    let lines: Vec<_> = message.lines().collect();
+    assert_eq!(lines.len(), 3);
+    assert_eq!(lines[0], "unmet expectations:");
+    assert!(lines[1].starts_with(&format!(
+        "Cat::meow was expected to be called 1 times with Eq(3), but called 0 times, added at {}:",
+        file!()
+    )));
+    assert!(lines[2].starts_with(
+        "Cat::weight was expected to be called 0 times with Eq(()), but called 1 times, added at "
+    ));
+}
+
+#[test]
+fn verified_when_last_clone_is_dropped() {
+    let mut cat = mry::new!(Cat {
+        name: "Tama".into()
+    });
+    cat.mock_meow(3).expect(1).returns("mocked".into());
+    let mry = cat.mry.clone();
+    drop(cat);
+
+    let message = catch_unwind(AssertUnwindSafe(|| drop(mry))).unwrap_err();
+    assert!(message
+        .downcast_ref::<String>()
+        .unwrap()
+        .contains("Cat::meow was expected to be called 1 times"));
+}
+
+#[test]
+#[should_panic(expected = "meow failed")]
+fn no_double_panic() {
+    let mut cat = mry::new!(Cat {
+        name: "Tama".into()
+    });
+    cat.mock_meow(3).expect(1).returns("mocked".into());
+
+    panic!("meow failed");
+}
+
+#[test]
+#[should_panic(expected = "hello was expected to be called 1 times with Eq(3), but called 0 times")]
+#[mry::lock(hello)]
+fn static_function() {
+    mock_hello(3).expect(1).returns("mocked".into());
+}
+
+#[mry::lock(hello, goodbye)]
+fn expect_hello_and_goodbye() {
+    mock_hello(3).expect(1).returns("mocked".into());
+    mock_goodbye(2).expect(1).returns("mocked".into());
+}
+
+#[test]
+fn static_functions_are_verified_at_once() {
+    let message = catch_unwind(expect_hello_and_goodbye)
+        .unwrap_err()
+        .downcast::<String>()
+        .unwrap();
+
+    assert!(message.starts_with("unmet expectations:\n"));
+    assert!(
+        message.contains("hello was expected to be called 1 times with Eq(3), but called 0 times")
+    );
+    assert!(message
+        .contains("goodbye was expected to be called 1 times with Eq(2), but called 0 times"));
+}
+
+#[test]
+#[should_panic(expected = "hello was expected to be called 1 times with Eq(3), but called 0 times")]
+#[mry::lock(hello, goodbye)]
+fn no_double_panic_with_unused_lock() {
+    mock_hello(3).expect(1).returns("mocked".into());
+}
@@ -0,0 +1,165 @@
+use mry::Fallback;
+
+#[mry::mry]
+#[derive(Default)]
+struct Cat {
+    name: String,
+}
+
+#[mry::mry]
+impl Cat {
+    fn meow(&self, count: usize) -> String {
+        format!("{}: {}", self.name, "meow".repeat(count))
+    }
+
+    fn weight(&self) -> usize {
+        4
+    }
+
+    fn greet(&self, base: &str) -> String {
+        format!("{} {}", base, self.name)
+    }
+
+    async fn purr(&self, count: usize) -> String {
+        "purr".repeat(count)
+    }
+}
+
+#[derive(Debug)]
+struct Fish;
+
+#[mry::mry]
+impl Cat {
+    fn catch(&self) -> Fish {
+        Fish
+    }
+}
+
+#[mry::mry(default = "nice")]
+trait Dog {
+    fn bark(&self, count: usize) -> String;
+    fn weight(&self) -> usize;
+}
+
+#[mry::mry(default = "real")]
+trait Bird {
+    fn tweet(&self) -> String {
+        "tweet".into()
+    }
+}
+
+#[test]
+fn nice_object() {
+    let mut cat = mry::new!(Cat {
+        name: "Tama".into()
+    });
+    cat.mry.fallback(Fallback::Nice);
+    cat.mock_weight().returns(5);
+
+    assert_eq!(cat.meow(2), String::new());
+    assert_eq!(cat.greet("Hi"), String::new());
+    assert_eq!(cat.weight(), 5);
+
+    cat.mock_meow(2).assert_called(1);
+}
+
+#[async_std::test]
+async fn nice_async() {
+    let mut cat = mry::new!(Cat {
+        name: "Tama".into()
+    });
+    cat.mry.fallback(Fallback::Nice);
+
+    assert_eq!(cat.purr(2).await, String::new());
+}
+
+#[test]
+#[should_panic(
+    expected = "mock not found for Cat::catch, and the output type does not implement Default"
+)]
+fn nice_without_default() {
+    let mut cat = mry::new!(Cat {
+        name: "Tama".into()
+    });
+    cat.mry.fallback(Fallback::Nice);
+
+    cat.catch();
+}
+
+#[test]
+fn method_fallback() {
+    let mut cat = mry::new!(Cat {
+        name: "Tama".into()
+    });
+    cat.mry.fallback(Fallback::Nice);
+    cat.mock_meow(mry::Any).fallback(Fallback::RealImpl);
+    cat.mock_meow(1).returns("mocked".into());
+
+    assert_eq!(cat.meow(1), "mocked".to_string());
+    assert_eq!(cat.meow(2), "Tama: meowmeow".to_string());
+    assert_eq!(cat.weight(), 0);
+}
+
+#[test]
+fn method_fallback_with() {
+    let mut cat = mry::new!(Cat {
+        name: "Tama".into()
+    });
+    cat.mock_meow(mry::Any)
+        .fallback_with(|count| format!("fallback {}", count));
+    cat.mock_meow(1).returns("mocked".into());
+
+    assert_eq!(cat.meow(1), "mocked".to_string());
+    assert_eq!(cat.meow(2), "fallback 2".to_string());
+}
+
+#[test]
+#[should_panic(expected = "mock not found for Cat::meow")]
+fn method_fallback_strict() {
+    let mut cat = mry::spy!(Cat {
+        name: "Tama".into()
+    });
+    cat.mock_meow(mry::Any).fallback(Fallback::Strict);
+
+    assert_eq!(cat.weight(), 4);
+    cat.meow(1);
+}
+
+#[test]
+fn default_fallback_per_test() {
+    let _fallback = mry::default_fallback(Fallback::Nice);
+    let mut cat = mry::new!(Cat {
+        name: "Tama".into()
+    });
+    cat.mock_weight().returns(5);
+
+    assert_eq!(cat.meow(2), String::new());
+}
+
+#[test]
+fn object_fallback_over_default_fallback() {
+    let _fallback = mry::default_fallback(Fallback::Nice);
+    let mut cat = mry::spy!(Cat {
+        name: "Tama".into()
+    });
+    cat.mock_weight().returns(5);
+
+    assert_eq!(cat.meow(2), "Tama: meowmeow".to_string());
+}
+
+#[test]
+fn nice_trait_mock() {
+    let mut dog = MockDog::default();
+    dog.mock_bark(1).returns("bow".into());
+
+    assert_eq!(dog.bark(1), "bow".to_string());
+    assert_eq!(dog.bark(2), String::new());
+    assert_eq!(dog.weight(), 0);
+}
+
+#[test]
+fn real_trait_mock() {
+    let bird = MockBird::default();
+
+    assert_eq!(bird.tweet(), "tweet".to_string());
+}
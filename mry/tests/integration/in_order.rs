@@ -0,0 +1,82 @@
+#[mry::mry]
+#[derive(Default)]
+struct Db {}
+
+#[mry::mry]
+impl Db {
+    fn begin(&self) {}
+
+    fn insert(&self, _value: usize) {}
+
+    fn commit(&self) {}
+}
+
+#[mry::mry]
+trait Logger {
+    fn log(&self, message: String);
+}
+
+#[mry::mry]
+fn notify(_count: usize) {}
+
+#[test]
+fn in_order() {
+    let mut db = mry::spy!(Db {});
+
+    db.begin();
+    db.insert(1);
+    db.insert(2);
+    db.commit();
+
+    mry::InOrder::new()
+        .then(db.mock_begin())
+        .then(db.mock_insert(2))
+        .then(db.mock_commit())
+        .verify();
+}
+
+#[test]
+#[should_panic(
+    expected = "calls are not in order: Db::commit with Eq(()) was not called after Db::insert with Eq(1)"
+)]
+fn not_in_order() {
+    let mut db = mry::spy!(Db {});
+
+    db.begin();
+    db.commit();
+    db.insert(1);
+
+    mry::sequence!(db.mock_begin(), db.mock_insert(1), db.mock_commit());
+}
+
+#[test]
+#[mry::lock(notify)]
+fn across_objects_and_static_functions() {
+    let mut db = mry::spy!(Db {});
+    let mut logger = MockLogger::default();
+    logger.mock_log(mry::Any).returns(());
+    mock_notify(mry::Any).returns(());
+
+    db.begin();
+    logger.log("begin".into());
+    notify(1);
+    db.commit();
+
+    mry::sequence!(
+        db.mock_begin(),
+        logger.mock_log("begin"),
+        mock_notify(1),
+        db.mock_commit(),
+    );
+}
+
+#[test]
+#[should_panic(expected = "calls are not in order: Logger::log with Eq(\"end\") was not called")]
+fn not_called() {
+    let mut db = mry::spy!(Db {});
+    let mut logger = MockLogger::default();
+
+    db.begin();
+
+    mry::sequence!(db.mock_begin(), logger.mock_log("end"));
+}
@@ -1,12 +1,34 @@
+mod assertion_message;
+mod async_behavior;
 mod async_method;
 mod async_trait;
+mod call_record;
+mod calls;
+mod captor;
+mod expect;
+mod fallback;
 mod function_style_macro;
 mod impl_trait;
+mod in_order;
 mod iterator;
+mod many_args;
+mod matchers;
+mod mock_not_found;
 mod mock_trait;
+mod mut_reference;
 mod nested_mock;
 mod not_clone;
+mod panics;
 mod partial_mock;
+mod pattern;
+mod real_impl;
 mod reference_and_pattern;
+mod reset;
+mod result_and_option;
+mod rule_order;
+mod sequence;
 mod simple_case;
+mod spy;
 mod static_function;
+mod strict;
+mod times;
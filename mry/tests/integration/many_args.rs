@@ -0,0 +1,104 @@
+#[mry::mry]
+#[derive(Default)]
+struct Service {}
+
+#[mry::mry]
+impl Service {
+    #[allow(clippy::too_many_arguments)]
+    fn six(&self, a: u8, b: u8, c: u8, d: u8, e: u8, f: String) -> String {
+        format!("{}{}", a + b + c + d + e, f)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn sixteen(
+        &self,
+        a: u8,
+        b: u8,
+        c: u8,
+        d: u8,
+        e: u8,
+        f: u8,
+        g: u8,
+        h: u8,
+        i: u8,
+        j: u8,
+        k: u8,
+        l: u8,
+        m: u8,
+        n: u8,
+        o: u8,
+        p: String,
+    ) -> String {
+        format!(
+            "{}{}",
+            a + b + c + d + e + f + g + h + i + j + k + l + m + n + o,
+            p
+        )
+    }
+}
+
+#[test]
+fn six_args() {
+    let mut service = mry::new!(Service {});
+    service
+        .mock_six(1, mry::Any, mry::Any, mry::Any, mry::Any, "a")
+        .returns_with(|a, b, c, d, e, f| format!("{}{}{}{}{}{}", a, b, c, d, e, f));
+
+    assert_eq!(service.six(1, 2, 3, 4, 5, "a".into()), "12345a".to_string());
+    service
+        .mock_six(1, 2, mry::Any, mry::Any, mry::Any, mry::Any)
+        .assert_called(1);
+}
+
+#[test]
+fn sixteen_args() {
+    let mut service = mry::new!(Service {});
+    service
+        .mock_sixteen(
+            1,
+            mry::Any,
+            mry::Any,
+            mry::Any,
+            mry::Any,
+            mry::Any,
+            mry::Any,
+            mry::Any,
+            mry::Any,
+            mry::Any,
+            mry::Any,
+            mry::Any,
+            mry::Any,
+            mry::Any,
+            mry::matchers::gt(10),
+            "p",
+        )
+        .returns_with(|a, _, _, _, _, _, _, _, _, _, _, _, _, _, o, p| format!("{}{}{}", a, o, p));
+
+    assert_eq!(
+        service.sixteen(1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 11, "p".into()),
+        "111p".to_string()
+    );
+
+    let logs = service
+        .mock_sixteen(
+            mry::Any,
+            mry::Any,
+            mry::Any,
+            mry::Any,
+            mry::Any,
+            mry::Any,
+            mry::Any,
+            mry::Any,
+            mry::Any,
+            mry::Any,
+            mry::Any,
+            mry::Any,
+            mry::Any,
+            mry::Any,
+            11,
+            mry::Any,
+        )
+        .assert_called(1);
+    assert_eq!((logs[0].a, logs[0].o), (1, 11));
+    assert_eq!(logs[0].p, "p".to_string());
+}
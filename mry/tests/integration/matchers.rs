@@ -0,0 +1,144 @@
+#[mry::mry]
+#[derive(Default)]
+struct Cat {
+    name: String,
+}
+
+#[mry::mry]
+impl Cat {
+    fn meow(&self, count: usize) -> String {
+        format!("{}: {}", self.name, "meow".repeat(count))
+    }
+
+    fn read(&self, path: String, count: usize) -> String {
+        format!("{}: {}", path, count)
+    }
+
+    fn feed(&self, food: Option<String>, amounts: Vec<u8>) -> bool {
+        food.is_some() && !amounts.is_empty()
+    }
+}
+
+#[test]
+fn pred_returns() {
+    let mut cat = mry::new!(Cat {
+        name: "Tama".into()
+    });
+    cat.mock_meow(mry::pred(|count: &usize| *count > 3))
+        .returns("Many".to_string());
+    cat.mock_meow(mry::Any).returns("Few".to_string());
+
+    assert_eq!(cat.meow(4), "Many".to_string());
+    assert_eq!(cat.meow(3), "Few".to_string());
+}
+
+#[test]
+fn pred_mixed_with_values() {
+    let mut cat = mry::new!(Cat {
+        name: "Tama".into()
+    });
+    cat.mock_read(mry::pred(|path: &String| path.ends_with(".json")), 2)
+        .returns("json".to_string());
+
+    assert_eq!(cat.read("a.json".into(), 2), "json".to_string());
+}
+
+#[test]
+fn pred_assert_called() {
+    let mut cat = mry::new!(Cat {
+        name: "Tama".into()
+    });
+    cat.mock_meow(mry::Any).returns("Called".to_string());
+
+    cat.meow(1);
+    cat.meow(4);
+    cat.meow(5);
+
+    let calls = cat
+        .mock_meow(mry::pred_named("count > 3", |count| *count > 3))
+        .assert_called(2);
+    assert_eq!(
+        calls.iter().map(|call| call.count).collect::<Vec<_>>(),
+        vec![4, 5]
+    );
+}
+
+#[test]
+#[should_panic(expected = "Cat::meow was not called")]
+fn pred_assert_called_fails() {
+    let mut cat = mry::new!(Cat {
+        name: "Tama".into()
+    });
+    cat.mock_meow(mry::Any).returns("Called".to_string());
+
+    cat.meow(1);
+
+    cat.mock_meow(mry::pred(|count: &usize| *count > 3))
+        .assert_called(1);
+}
+
+#[test]
+fn builtin_matchers_returns() {
+    use mry::matchers::*;
+
+    let mut cat = mry::new!(Cat {
+        name: "Tama".into()
+    });
+    cat.mock_read(ends_with(".json"), in_range(1..3))
+        .returns("json".to_string());
+    cat.mock_read(starts_with("/tmp"), gt(2))
+        .returns("tmp".to_string());
+
+    assert_eq!(cat.read("a.json".into(), 2), "json".to_string());
+    assert_eq!(cat.read("/tmp/a".into(), 3), "tmp".to_string());
+}
+
+#[test]
+fn builtin_matchers_assert_called() {
+    use mry::matchers::*;
+
+    let mut cat = mry::new!(Cat {
+        name: "Tama".into()
+    });
+    cat.mock_feed(mry::Any, mry::Any).returns(true);
+
+    cat.feed(Some("fish".into()), vec![1, 2]);
+    cat.feed(None, vec![]);
+
+    cat.mock_feed(is_some(), contains(2)).assert_called(1);
+    cat.mock_feed(is_none(), len(0)).assert_called(1);
+    cat.mock_feed(mry::Any, len(3)).assert_called(0);
+}
+
+#[test]
+fn combinators_returns() {
+    use mry::matchers::*;
+
+    let mut cat = mry::new!(Cat {
+        name: "Tama".into()
+    });
+    cat.mock_read(mry::any_of!["a", "b"], mry::all_of![gt(1), not(3)])
+        .returns("matched".to_string());
+    cat.mock_read(ends_with(".json").or(ends_with(".toml")), lt(2).and(ne(0)))
+        .returns("config".to_string());
+
+    assert_eq!(cat.read("b".into(), 2), "matched".to_string());
+    assert_eq!(cat.read("a.toml".into(), 1), "config".to_string());
+}
+
+#[test]
+#[should_panic(expected = "matcher: (AnyOf(Eq(\"a\"), Eq(\"b\")), AllOf(Gt(1), Not(Eq(3))))")]
+fn combinators_assert_called_fails() {
+    use mry::matchers::*;
+
+    let mut cat = mry::new!(Cat {
+        name: "Tama".into()
+    });
+    cat.mock_read(mry::Any, mry::Any)
+        .returns("called".to_string());
+
+    cat.read("a".into(), 3);
+
+    cat.mock_read(mry::any_of!["a", "b"], mry::all_of![gt(1), not(3)])
+        .assert_called(1);
+}
@@ -0,0 +1,76 @@
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+#[mry::mry]
+#[derive(Default)]
+struct Cat {}
+
+#[mry::mry]
+impl Cat {
+    fn meow(&self, base: String, count: usize) -> String {
+        base.repeat(count)
+    }
+
+    fn greet(&self, name: &str) -> String {
+        format!("meow, {}", name)
+    }
+}
+
+#[test]
+fn shows_rules_and_hint() {
+    let mut cat = mry::new!(Cat {});
+    let added = line!() + 1;
+    cat.mock_meow("a", mry::Any).returns("mocked".into());
+
+    let called = line!() + 1;
+    let message = catch_unwind(AssertUnwindSafe(|| cat.meow("b".into(), 3)))
+        .unwrap_err()
+        .downcast::<String>()
+        .unwrap();
+
+    assert!(message.starts_with(&format!(
+        r#"mock not found for Cat::meow
+arguments: ("b", 3)
+rules:
+  #0 (Eq("a"), Any) => Const("mocked"), added at {}:{}:"#,
+        file!(),
+        added
+    )));
+    assert!(message.contains(&format!(
+        r#"
+    ✗ base: "b" doesn't match Eq("a")
+hint: did you forget mock_meow(Any, Any)?
+called at {}:{}:"#,
+        file!(),
+        called
+    )));
+}
+
+#[test]
+fn shows_where_a_method_taking_references_is_called() {
+    let mut cat = mry::new!(Cat {});
+    cat.mock_greet("Tama").returns("mocked".into());
+
+    let called = line!() + 1;
+    let message = catch_unwind(AssertUnwindSafe(|| cat.greet("Pochi")))
+        .unwrap_err()
+        .downcast::<String>()
+        .unwrap();
+
+    assert!(message.contains(&format!("\ncalled at {}:{}:", file!(), called)));
+}
+
+#[test]
+fn describing_rules_keeps_values() {
+    let mut cat = mry::new!(Cat {});
+    cat.mock_meow("a", mry::Any)
+        .returns_seq(vec!["first".into(), "second".into()]);
+
+    let message = catch_unwind(AssertUnwindSafe(|| cat.meow("b".into(), 1)))
+        .unwrap_err()
+        .downcast::<String>()
+        .unwrap();
+
+    assert!(message.contains(r#"=> Const("first")"#));
+    assert_eq!(cat.meow("a".into(), 1), "first".to_string());
+    assert_eq!(cat.meow("a".into(), 1), "second".to_string());
+}
@@ -0,0 +1,99 @@
+#[mry::mry]
+#[derive(Default)]
+struct Reader {}
+
+#[mry::mry]
+impl Reader {
+    fn read(&mut self, buf: &mut [u8]) -> usize {
+        0
+    }
+
+    fn read_to_end(&self, buf: &mut Vec<u8>) -> usize {
+        0
+    }
+
+    fn decode(&self, input: &str, out: &mut String, count: &mut usize) -> bool {
+        false
+    }
+
+    async fn read_async(&self, buf: &mut Vec<u8>) -> usize {
+        0
+    }
+}
+
+#[test]
+fn returns_with_mut_slice() {
+    let mut reader = Reader::default();
+    reader
+        .mock_read(mry::Any)
+        .returns_with_mut(|buf: &mut Vec<u8>| {
+            buf[..3].copy_from_slice(b"abc");
+            3
+        });
+    let mut buf = [0; 4];
+
+    assert_eq!(reader.read(&mut buf), 3);
+    assert_eq!(&buf, b"abc\0");
+}
+
+#[test]
+fn returns_with_mut_logs_snapshot() {
+    let mut reader = Reader::default();
+    reader
+        .mock_read_to_end(mry::Any)
+        .returns_with_mut(|buf: &mut Vec<u8>| {
+            buf.extend(b"abc");
+            3
+        });
+    let mut buf = vec![b'x'];
+
+    assert_eq!(reader.read_to_end(&mut buf), 3);
+    assert_eq!(buf, b"xabc".to_vec());
+    assert_eq!(
+        reader.mock_read_to_end(vec![b'x']).assert_called(1)[0].buf,
+        vec![b'x']
+    );
+}
+
+#[test]
+fn returns_with_mut_multiple_args() {
+    let mut reader = Reader::default();
+    reader
+        .mock_decode("meow", mry::Any, mry::Any)
+        .returns_with_mut(|input: &mut String, out: &mut String, count: &mut usize| {
+            *out = input.to_uppercase();
+            *count += 1;
+            true
+        });
+    let mut out = String::new();
+    let mut count = 1;
+
+    assert!(reader.decode("meow", &mut out, &mut count));
+    assert_eq!(out, "MEOW".to_string());
+    assert_eq!(count, 2);
+}
+
+#[test]
+fn calls_real_impl_keeps_arguments() {
+    let mut reader = Reader::default();
+    reader.mock_read_to_end(mry::Any).calls_real_impl();
+    let mut buf = vec![1];
+
+    assert_eq!(reader.read_to_end(&mut buf), 0);
+    assert_eq!(buf, vec![1]);
+}
+
+#[async_std::test]
+async fn returns_with_mut_async() {
+    let mut reader = Reader::default();
+    reader
+        .mock_read_async(mry::Any)
+        .returns_with_mut(|buf: &mut Vec<u8>| {
+            buf.push(1);
+            1
+        });
+    let mut buf = vec![];
+
+    assert_eq!(reader.read_async(&mut buf).await, 1);
+    assert_eq!(buf, vec![1]);
+}
@@ -0,0 +1,50 @@
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+#[mry::mry]
+#[derive(Default)]
+struct Store {}
+
+#[mry::mry]
+impl Store {
+    fn save(&self, key: usize, value: String) -> bool {
+        true
+    }
+}
+
+#[test]
+#[should_panic(expected = "disk full")]
+fn panics() {
+    let mut store = Store::default();
+    store.mock_save(mry::Any, mry::Any).panics("disk full");
+
+    store.save(1, "a".into());
+}
+
+#[test]
+fn panics_with() {
+    let mut store = Store::default();
+    store
+        .mock_save(mry::Any, mry::Any)
+        .panics_with(|(key, value)| format!("cannot save {} to {}", value, key));
+
+    let payload = catch_unwind(AssertUnwindSafe(|| store.save(1, "a".into()))).unwrap_err();
+
+    assert_eq!(
+        payload.downcast_ref::<String>().unwrap(),
+        "cannot save a to 1"
+    );
+}
+
+#[test]
+fn panics_once_then_recovers() {
+    let mut store = Store::default();
+    store
+        .mock_save(mry::Any, mry::Any)
+        .once()
+        .panics("disk full");
+    store.mock_save(mry::Any, mry::Any).calls_real_impl();
+
+    assert!(catch_unwind(AssertUnwindSafe(|| store.save(1, "a".into()))).is_err());
+    assert!(store.save(1, "a".into()));
+    store.mock_save(mry::Any, mry::Any).assert_called(2);
+}
@@ -0,0 +1,126 @@
+// no PartialEq
+#[derive(Debug, Clone)]
+struct Order {
+    id: u32,
+    name: String,
+    items: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+enum Cmd {
+    Move(i32, i32),
+    Stop,
+}
+
+#[mry::mry]
+#[derive(Default)]
+struct Shop {}
+
+#[mry::mry]
+impl Shop {
+    fn place(&self, order: Option<Order>) -> bool {
+        order.is_some()
+    }
+
+    fn run(&self, cmd: Cmd, count: usize) -> usize {
+        count
+    }
+}
+
+#[test]
+fn pattern_returns() {
+    let mut shop = mry::new!(Shop {});
+    shop.mock_place(mry::pat!(Some(Order { id: 7, .. })))
+        .returns(false);
+    shop.mock_place(mry::Any).returns(true);
+
+    assert!(!shop.place(Some(Order {
+        id: 7,
+        name: "tuna".into(),
+        items: vec!["fish".into()],
+    })));
+    assert!(shop.place(Some(Order {
+        id: 8,
+        name: "tuna".into(),
+        items: vec![],
+    })));
+    assert!(shop.place(None));
+}
+
+#[test]
+fn pattern_with_guard() {
+    let mut shop = mry::new!(Shop {});
+    shop.mock_run(mry::pat!(Cmd::Move(x, _) if *x > 0), mry::Any)
+        .returns(1);
+    shop.mock_run(mry::pat!(Cmd::Move(..) | Cmd::Stop), 2)
+        .returns(2);
+
+    assert_eq!(shop.run(Cmd::Move(1, 0), 0), 1);
+    assert_eq!(shop.run(Cmd::Move(-1, 0), 2), 2);
+    assert_eq!(shop.run(Cmd::Stop, 2), 2);
+}
+
+#[test]
+fn pattern_with_ref_binding() {
+    let mut shop = mry::new!(Shop {});
+    shop.mock_place(mry::pat!(Some(Order { ref items, .. }) if items.len() > 1))
+        .returns(false);
+
+    assert!(!shop.place(Some(Order {
+        id: 7,
+        name: "tuna".into(),
+        items: vec!["fish".into(), "milk".into()],
+    })));
+}
+
+#[test]
+fn pattern_with_guard_on_non_copy_field() {
+    let mut shop = mry::new!(Shop {});
+    shop.mock_place(mry::pat!(Some(Order { name, .. }) if name.starts_with("a")))
+        .returns(false);
+    shop.mock_place(mry::Any).returns(true);
+
+    assert!(!shop.place(Some(Order {
+        id: 7,
+        name: "anchovy".into(),
+        items: vec![],
+    })));
+    assert!(shop.place(Some(Order {
+        id: 7,
+        name: "tuna".into(),
+        items: vec![],
+    })));
+}
+
+#[test]
+fn pattern_assert_called() {
+    let mut shop = mry::new!(Shop {});
+    shop.mock_run(mry::Any, mry::Any).returns(0);
+
+    shop.run(Cmd::Move(1, 2), 1);
+    shop.run(Cmd::Stop, 1);
+    shop.run(Cmd::Move(3, 4), 1);
+
+    let logs = shop
+        .mock_run(mry::pat!(Cmd::Move(_, y) if *y > 3), 1)
+        .assert_called(1);
+    assert!(matches!(
+        logs[0],
+        ShopRunCall {
+            cmd: Cmd::Move(3, 4),
+            count: 1
+        }
+    ));
+}
+
+#[test]
+#[should_panic(expected = "Pat(Cmd::Stop)")]
+fn pattern_assert_called_fails() {
+    let mut shop = mry::new!(Shop {});
+    shop.mock_run(mry::Any, mry::Any).returns(0);
+
+    shop.run(Cmd::Move(1, 2), 1);
+
+    shop.mock_run(mry::pat!(Cmd::Stop), mry::Any)
+        .assert_called(1);
+}
@@ -0,0 +1,100 @@
+use std::time::Duration;
+
+#[mry::mry]
+#[derive(Default)]
+struct Cat {
+    name: String,
+}
+
+#[mry::mry]
+impl Cat {
+    fn meow(&self, count: usize) -> String {
+        format!("{}: {}", self.name, "meow".repeat(count))
+    }
+
+    fn rename(&mut self, name: String) -> String {
+        std::mem::replace(&mut self.name, name)
+    }
+
+    fn loud(&self, count: usize) -> String {
+        self.meow(count).to_uppercase()
+    }
+
+    fn greet(&self, base: &str) -> String {
+        base.to_string()
+    }
+
+    async fn purr(&self, count: usize) -> String {
+        async_std::task::sleep(Duration::from_millis(1)).await;
+        "purr".repeat(count)
+    }
+}
+
+#[test]
+fn calls_real_impl_with() {
+    let mut cat = mry::new!(Cat {
+        name: "Tama".into()
+    });
+    cat.mock_meow(mry::Any)
+        .calls_real_impl_with(|count| count * 2);
+
+    assert_eq!(cat.meow(1), "Tama: meowmeow".to_string());
+    // logs the original arguments
+    cat.mock_meow(1).assert_called(1);
+}
+
+#[test]
+fn wraps_real_impl() {
+    let mut cat = mry::new!(Cat {
+        name: "Tama".into()
+    });
+    cat.mock_meow(mry::Any)
+        .wraps_real_impl(|count, real| format!("<{}>", real(count + 1)));
+
+    assert_eq!(cat.meow(1), "<Tama: meowmeow>".to_string());
+}
+
+#[test]
+fn wraps_real_impl_with_mut_self() {
+    let mut cat = mry::new!(Cat {
+        name: "Tama".into()
+    });
+    cat.mock_rename(mry::Any)
+        .wraps_real_impl(|name, real| real(name.to_uppercase()));
+
+    assert_eq!(cat.rename("Mike".into()), "Tama".to_string());
+    assert_eq!(cat.name, "MIKE".to_string());
+}
+
+#[test]
+fn real_impl_calls_other_mocks() {
+    let mut cat = mry::new!(Cat {
+        name: "Tama".into()
+    });
+    cat.mock_meow(mry::Any)
+        .wraps_real_impl(|count, real| real(count));
+    cat.mock_purr(mry::Any).returns("purr".to_string());
+
+    assert_eq!(cat.meow(1), "Tama: meow".to_string());
+}
+
+#[async_std::test]
+async fn calls_real_impl_with_async() {
+    let mut cat = Cat::default();
+    cat.mock_purr(mry::Any)
+        .calls_real_impl_with(|count| count + 1);
+
+    assert_eq!(cat.purr(1).await, "purrpurr".to_string());
+}
+
+#[test]
+#[should_panic(
+    expected = "calls_real_impl_with and wraps_real_impl are not supported for Cat::greet"
+)]
+fn not_supported_with_references() {
+    let mut cat = Cat::default();
+    cat.mock_greet(mry::Any)
+        .calls_real_impl_with(|base| base + "!");
+
+    cat.greet("hello");
+}
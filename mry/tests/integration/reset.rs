@@ -0,0 +1,95 @@
+#[mry::mry]
+#[derive(Default)]
+struct Cat {
+    name: String,
+}
+
+#[mry::mry]
+impl Cat {
+    fn meow(&self, count: usize) -> String {
+        format!("{}: {}", self.name, "meow".repeat(count))
+    }
+
+    fn weight(&self) -> usize {
+        4
+    }
+}
+
+#[mry::mry]
+fn hello(count: usize) -> String {
+    "hello".repeat(count)
+}
+
+#[test]
+fn table_driven() {
+    let mut cat = mry::spy!(Cat {
+        name: "Tama".into()
+    });
+    for (count, expected) in [(1, "one"), (2, "two")] {
+        cat.mry.reset_all();
+        cat.mock_meow(count).returns(expected.into());
+
+        assert_eq!(cat.meow(count), expected.to_string());
+        assert_eq!(cat.meow(3), "Tama: meowmeowmeow".to_string());
+
+        cat.mock_meow(mry::Any).assert_called(2);
+    }
+}
+
+#[test]
+#[should_panic(expected = "mock not found for Cat::meow")]
+fn reset_removes_rules() {
+    let mut cat = mry::new!(Cat {
+        name: "Tama".into()
+    });
+    cat.mock_meow(mry::Any).returns("mocked".into());
+    cat.mock_weight().returns(5);
+    cat.mock_meow(mry::Any).reset();
+
+    assert_eq!(cat.weight(), 5);
+    cat.meow(1);
+}
+
+#[test]
+fn reset_removes_logs() {
+    let mut cat = mry::new!(Cat {
+        name: "Tama".into()
+    });
+    cat.mock_meow(mry::Any).returns("mocked".into());
+    cat.meow(1);
+    cat.mock_meow(mry::Any).reset();
+
+    cat.mock_meow(mry::Any).assert_called(0);
+}
+
+#[test]
+fn clear_logs_keeps_rules() {
+    let mut cat = mry::new!(Cat {
+        name: "Tama".into()
+    });
+    cat.mock_meow(mry::Any).returns("mocked".into());
+    cat.mock_weight().returns(5);
+    cat.meow(1);
+    cat.weight();
+    cat.mock_meow(mry::Any).clear_logs();
+
+    cat.mock_meow(mry::Any).assert_called(0);
+    cat.mock_weight().assert_called(1);
+    assert_eq!(cat.meow(1), "mocked".to_string());
+
+    cat.mry.clear_logs();
+    cat.mock_meow(mry::Any).assert_called(0);
+    cat.mock_weight().assert_called(0);
+}
+
+#[test]
+#[mry::lock(hello)]
+fn reset_static_function() {
+    mock_hello(mry::Any).returns("mocked".into());
+    assert_eq!(hello(1), "mocked".to_string());
+
+    mock_hello(mry::Any).reset();
+    mock_hello(mry::Any).calls_real_impl();
+    assert_eq!(hello(1), "hello".to_string());
+    mock_hello(mry::Any).assert_called(1);
+}
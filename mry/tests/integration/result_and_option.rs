@@ -0,0 +1,61 @@
+// no clone
+#[derive(Debug, PartialEq)]
+struct DiskError(String);
+
+#[mry::mry]
+#[derive(Default)]
+struct Store {}
+
+#[mry::mry]
+impl Store {
+    fn save(&self, key: usize) -> Result<usize, DiskError> {
+        Ok(key)
+    }
+
+    fn find(&self, key: usize) -> Option<String> {
+        None
+    }
+}
+
+#[test]
+fn returns_ok() {
+    let mut store = Store::default();
+    store.mock_save(mry::Any).returns_ok(42);
+
+    assert_eq!(store.save(1), Ok(42));
+    assert_eq!(store.save(2), Ok(42));
+}
+
+#[test]
+fn returns_err_with() {
+    let mut store = Store::default();
+    store
+        .mock_save(mry::Any)
+        .returns_err_with(|| DiskError("full".into()));
+
+    assert_eq!(store.save(1), Err(DiskError("full".into())));
+    assert_eq!(store.save(1), Err(DiskError("full".into())));
+}
+
+#[test]
+fn returns_err_then_ok() {
+    let mut store = Store::default();
+    store
+        .mock_save(mry::Any)
+        .once()
+        .returns_err_with(|| DiskError("busy".into()));
+    store.mock_save(mry::Any).calls_real_impl();
+
+    assert_eq!(store.save(1), Err(DiskError("busy".into())));
+    assert_eq!(store.save(1), Ok(1));
+}
+
+#[test]
+fn returns_some() {
+    let mut store = Store::default();
+    store.mock_find(1).returns_some("one".to_string());
+    store.mock_find(mry::Any).returns_none();
+
+    assert_eq!(store.find(1), Some("one".to_string()));
+    assert_eq!(store.find(2), None);
+}
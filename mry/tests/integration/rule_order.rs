@@ -0,0 +1,59 @@
+use mry::RuleOrder;
+
+#[mry::mry]
+#[derive(Default)]
+struct Cat {
+    name: String,
+}
+
+#[mry::mry]
+impl Cat {
+    fn meow(&self, count: usize) -> String {
+        format!("{}: {}", self.name, "meow".repeat(count))
+    }
+}
+
+fn setup() -> Cat {
+    let mut cat = mry::new!(Cat {
+        name: "Tama".into()
+    });
+    cat.mock_meow(mry::Any).returns("setup".into());
+    cat
+}
+
+#[test]
+fn first_wins_by_default() {
+    let mut cat = setup();
+    cat.mock_meow(mry::Any).returns("test".into());
+
+    assert_eq!(cat.meow(1), "setup".to_string());
+}
+
+#[test]
+fn last_wins() {
+    let mut cat = setup();
+    cat.mock_meow(mry::Any).rule_order(RuleOrder::LastWins);
+    cat.mock_meow(2).returns("two".into());
+
+    assert_eq!(cat.meow(1), "setup".to_string());
+    assert_eq!(cat.meow(2), "two".to_string());
+}
+
+#[test]
+fn default_rule_order() {
+    let _order = mry::default_rule_order(RuleOrder::LastWins);
+    let mut cat = setup();
+    cat.mock_meow(mry::Any).returns("test".into());
+
+    assert_eq!(cat.meow(1), "test".to_string());
+}
+
+#[test]
+fn replace() {
+    let mut cat = setup();
+    cat.mock_meow(2).returns("two".into());
+    cat.mock_meow(mry::Any).replace().returns("test".into());
+
+    assert_eq!(cat.meow(1), "test".to_string());
+    assert_eq!(cat.meow(2), "two".to_string());
+}
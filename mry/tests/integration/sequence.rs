@@ -0,0 +1,99 @@
+// no clone
+#[derive(Debug, PartialEq)]
+struct Token(u8);
+
+#[mry::mry]
+#[derive(Default)]
+struct Cat {
+    name: String,
+}
+
+#[mry::mry]
+impl Cat {
+    fn meow(&self, count: usize) -> String {
+        format!("{}: {}", self.name, "meow".repeat(count))
+    }
+
+    fn token(&self) -> Token {
+        Token(0)
+    }
+}
+
+#[test]
+fn returns_seq() {
+    let mut cat = mry::new!(Cat {
+        name: "Tama".into()
+    });
+    cat.mock_meow(mry::Any)
+        .returns_seq(vec!["a".to_string(), "b".to_string()]);
+    cat.mock_meow(mry::Any).returns("c".to_string());
+
+    assert_eq!(cat.meow(1), "a".to_string());
+    assert_eq!(cat.meow(1), "b".to_string());
+    assert_eq!(cat.meow(1), "c".to_string());
+    assert_eq!(cat.meow(1), "c".to_string());
+}
+
+#[test]
+fn returns_seq_not_clone() {
+    let mut cat = mry::new!(Cat {
+        name: "Tama".into()
+    });
+    cat.mock_token().returns_seq(vec![Token(1), Token(2)]);
+
+    assert_eq!(cat.token(), Token(1));
+    assert_eq!(cat.token(), Token(2));
+}
+
+#[test]
+fn returns_once() {
+    let mut cat = mry::new!(Cat {
+        name: "Tama".into()
+    });
+    cat.mock_meow(2).returns_once("once".to_string());
+    cat.mock_meow(mry::Any).calls_real_impl();
+
+    assert_eq!(cat.meow(2), "once".to_string());
+    assert_eq!(cat.meow(2), "Tama: meowmeow".to_string());
+}
+
+#[test]
+fn returns_iter() {
+    let mut cat = mry::new!(Cat {
+        name: "Tama".into()
+    });
+    cat.mock_token().returns_iter((1..).map(Token));
+
+    assert_eq!(cat.token(), Token(1));
+    assert_eq!(cat.token(), Token(2));
+    assert_eq!(cat.token(), Token(3));
+}
+
+#[test]
+#[should_panic(expected = "sequence exhausted for Cat::meow")]
+fn sequence_exhausted() {
+    let mut cat = mry::new!(Cat {
+        name: "Tama".into()
+    });
+    cat.mock_meow(mry::Any).returns_seq(vec!["a".to_string()]);
+
+    cat.meow(1);
+    cat.meow(1);
+}
+
+#[test]
+fn sequence_exhausted_shows_where_it_is_called() {
+    let mut cat = mry::new!(Cat {
+        name: "Tama".into()
+    });
+    cat.mock_meow(mry::Any).returns_seq(vec!["a".to_string()]);
+    cat.meow(1);
+
+    let called = line!() + 1;
+    let message = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| cat.meow(1)))
+        .unwrap_err()
+        .downcast::<String>()
+        .unwrap();
+
+    assert!(message.contains(&format!("\ncalled at {}:{}:", file!(), called)));
+}
@@ -0,0 +1,97 @@
+#[mry::mry]
+#[derive(Default)]
+struct Cat {
+    name: String,
+}
+
+#[mry::mry]
+impl Cat {
+    fn meow(&self, count: usize) -> String {
+        format!("{}: {}", self.name, "meow".repeat(count))
+    }
+
+    fn loud(&self, count: usize) -> String {
+        self.meow(count).to_uppercase()
+    }
+
+    fn greet(&self, base: &str) -> String {
+        format!("{} {}", base, self.name)
+    }
+
+    async fn purr(&self, count: usize) -> String {
+        "purr".repeat(count)
+    }
+}
+
+#[test]
+fn spy_calls_real_impl() {
+    let mut cat = mry::spy!(Cat {
+        name: "Tama".into()
+    });
+
+    assert_eq!(cat.loud(2), "TAMA: MEOWMEOW".to_string());
+
+    cat.mock_loud(2).assert_called(1);
+    cat.mock_meow(2).assert_called(1);
+}
+
+#[test]
+fn spy_uses_matching_rules() {
+    let mut cat = mry::spy!(Cat {
+        name: "Tama".into()
+    });
+    cat.mock_meow(1).returns("mocked".into());
+
+    assert_eq!(cat.loud(1), "MOCKED".to_string());
+    assert_eq!(cat.loud(2), "TAMA: MEOWMEOW".to_string());
+
+    cat.mock_meow(mry::Any).assert_called(2);
+}
+
+#[test]
+fn spy_after_construction() {
+    let mut cat = Cat {
+        name: "Tama".into(),
+        ..Default::default()
+    };
+    cat.mock_meow(1).returns("mocked".into());
+    cat.mry.spy();
+
+    assert_eq!(cat.meow(1), "mocked".to_string());
+    assert_eq!(cat.meow(2), "Tama: meowmeow".to_string());
+
+    cat.mock_meow(mry::Any).assert_called(2);
+}
+
+#[test]
+fn spy_with_references() {
+    let mut cat = mry::spy!(Cat {
+        name: "Tama".into()
+    });
+
+    assert_eq!(cat.greet("Hi"), "Hi Tama".to_string());
+
+    cat.mock_greet("Hi").assert_called(1);
+}
+
+#[async_std::test]
+async fn spy_async() {
+    let mut cat = mry::spy!(Cat {
+        name: "Tama".into()
+    });
+
+    assert_eq!(cat.purr(2).await, "purrpurr".to_string());
+
+    cat.mock_purr(2).assert_called(1);
+}
+
+#[test]
+#[should_panic(expected = "mock not found for Cat::loud")]
+fn not_spy_panics() {
+    let mut cat = mry::new!(Cat {
+        name: "Tama".into()
+    });
+    cat.mock_meow(1).returns("mocked".into());
+
+    cat.loud(1);
+}
@@ -0,0 +1,95 @@
+#[mry::mry]
+#[derive(Default)]
+struct Cat {
+    name: String,
+}
+
+#[mry::mry]
+impl Cat {
+    fn meow(&self, count: usize) -> String {
+        format!("{}: {}", self.name, "meow".repeat(count))
+    }
+
+    fn hiss() -> String {
+        "hiss".into()
+    }
+}
+
+#[mry::mry(strict)]
+trait Dog {
+    fn bark(&self, count: usize) -> String;
+}
+
+#[test]
+#[should_panic(
+    expected = "unused rules:\nCat::meow with Eq(2) never matched, added at mry/tests/integration/strict.rs:"
+)]
+fn strict_scope_reports_unused_rule() {
+    let _strict = mry::strict();
+    let mut cat = mry::new!(Cat {
+        name: "Tama".into()
+    });
+    cat.mock_meow(1).returns("Called".to_string());
+    cat.mock_meow(2).returns("Never".to_string());
+
+    assert_eq!(cat.meow(1), "Called".to_string());
+}
+
+#[test]
+#[should_panic(
+    expected = "unused rules:\nCat::meow with Eq(2) never matched, added at mry/tests/integration/strict.rs:"
+)]
+fn strict_object_reports_unused_rule() {
+    let mut cat = mry::new!(Cat {
+        name: "Tama".into()
+    });
+    cat.mry.strict();
+    cat.mock_meow(2).returns("Never".to_string());
+}
+
+#[test]
+#[should_panic(
+    expected = "unused rules:\nDog::bark with Any never matched, added at mry/tests/integration/strict.rs:"
+)]
+fn strict_trait_reports_unused_rule() {
+    let mut dog = MockDog::default();
+    dog.mock_bark(mry::Any).returns("bow".to_string());
+}
+
+#[test]
+#[mry::lock(Cat::hiss)]
+#[should_panic(
+    expected = "unused rules:\nCat::hiss with Eq(()) never matched, added at mry/tests/integration/strict.rs:"
+)]
+fn strict_scope_reports_unused_static_rule() {
+    let _strict = mry::strict();
+    Cat::mock_hiss().returns("Never".to_string());
+}
+
+#[test]
+fn used_rules_pass_in_strict_mode() {
+    let _strict = mry::strict();
+    let mut cat = mry::new!(Cat {
+        name: "Tama".into()
+    });
+    cat.mock_meow(1).returns("Called".to_string());
+
+    assert_eq!(cat.meow(1), "Called".to_string());
+}
+
+#[test]
+fn expected_rules_are_not_reported() {
+    let _strict = mry::strict();
+    let mut cat = mry::new!(Cat {
+        name: "Tama".into()
+    });
+    cat.mock_meow(2).expect(0).returns("Never".to_string());
+}
+
+#[test]
+fn rules_are_not_reported_outside_strict_mode() {
+    let mut cat = mry::new!(Cat {
+        name: "Tama".into()
+    });
+    cat.mock_meow(2).returns("Never".to_string());
+}
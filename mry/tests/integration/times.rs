@@ -0,0 +1,83 @@
+#[mry::mry]
+#[derive(Default)]
+struct Store {}
+
+#[mry::mry]
+impl Store {
+    fn save(&self, key: usize) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+#[test]
+fn times_falls_through() {
+    let mut store = Store::default();
+    store
+        .mock_save(mry::Any)
+        .times(2)
+        .returns(Err("busy".to_string()));
+    store.mock_save(mry::Any).returns(Ok(()));
+
+    assert_eq!(store.save(1), Err("busy".to_string()));
+    assert_eq!(store.save(1), Err("busy".to_string()));
+    assert_eq!(store.save(1), Ok(()));
+    assert_eq!(store.save(1), Ok(()));
+}
+
+#[test]
+fn once_falls_through_to_real_impl() {
+    let mut store = Store::default();
+    store
+        .mock_save(1)
+        .once()
+        .returns_with(|_| Err("busy".to_string()));
+    store.mock_save(mry::Any).calls_real_impl();
+
+    assert_eq!(store.save(1), Err("busy".to_string()));
+    assert_eq!(store.save(1), Ok(()));
+}
+
+#[test]
+#[should_panic(expected = "mock not found for Store::save")]
+fn once_retired() {
+    let mut store = Store::default();
+    store.mock_save(mry::Any).once().returns(Ok(()));
+
+    store.save(1).unwrap();
+    store.save(1).unwrap();
+}
+
+#[test]
+fn assert_consumed() {
+    let mut store = Store::default();
+    store.mock_save(mry::Any).once().returns(Ok(()));
+    store.mock_save(mry::Any).returns(Err("busy".to_string()));
+
+    store.save(1).unwrap();
+
+    store.mock_save(mry::Any).assert_consumed();
+}
+
+#[test]
+#[should_panic(expected = "Store::save has a rule not fully consumed")]
+fn assert_consumed_panics() {
+    let mut store = Store::default();
+    store.mock_save(mry::Any).times(2).returns(Ok(()));
+
+    store.save(1).unwrap();
+
+    store.mock_save(mry::Any).assert_consumed();
+}
+
+#[test]
+#[should_panic(expected = "Store::save has a rule not fully consumed")]
+fn exhausted_calls_do_not_consume() {
+    let mut store = Store::default();
+    store.mock_save(mry::Any).times(2).returns_once(Ok(()));
+    store.mock_save(mry::Any).returns(Err("busy".to_string()));
+
+    store.save(1).unwrap();
+    assert_eq!(store.save(1), Err("busy".to_string()));
+
+    store.mock_save(mry::Any).assert_consumed();
+}
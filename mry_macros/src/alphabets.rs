@@ -1,7 +1,12 @@
 use std::ops::Range;
 
+/// The maximum number of arguments of a mocked method
+pub(crate) const MAX_ARGS: usize = 16;
+
 pub(crate) fn alphabets(range: Range<usize>) -> impl Iterator<Item = Vec<&'static str>> {
-    let alphabet = vec!["A", "B", "C", "D", "E", "F", "G", "H"];
+    let alphabet = vec![
+        "A", "B", "C", "D", "E", "F", "G", "H", "I", "J", "K", "L", "M", "N", "O", "P",
+    ];
     range
         .into_iter()
         .map(move |index| alphabet[0..index].iter().cloned().collect())
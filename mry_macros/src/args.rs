@@ -0,0 +1,90 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::spanned::Spanned;
+use syn::{AttributeArgs, Error, Lit, Meta, NestedMeta};
+
+/// Arguments of `#[mry::mry(...)]`
+#[derive(Default)]
+pub(crate) struct Args {
+    /// `mry::Fallback` from `default = "strict" | "nice" | "real"`
+    pub fallback: Option<TokenStream>,
+    /// `strict`, which reports rules never matched
+    pub strict: bool,
+}
+
+pub(crate) fn parse(args: AttributeArgs) -> Result<Args, Error> {
+    let mut parsed = Args::default();
+    for arg in args {
+        match &arg {
+            NestedMeta::Meta(Meta::NameValue(name_value))
+                if name_value.path.is_ident("default") =>
+            {
+                let variant = match &name_value.lit {
+                    Lit::Str(lit) if lit.value() == "strict" => quote![Strict],
+                    Lit::Str(lit) if lit.value() == "nice" => quote![Nice],
+                    Lit::Str(lit) if lit.value() == "real" => quote![RealImpl],
+                    lit => {
+                        return Err(Error::new(
+                            lit.span(),
+                            "expected \"strict\", \"nice\", or \"real\"",
+                        ))
+                    }
+                };
+                parsed.fallback = Some(quote![mry::Fallback::#variant]);
+            }
+            NestedMeta::Meta(Meta::Path(path)) if path.is_ident("strict") => {
+                parsed.strict = true;
+            }
+            _ => return Err(Error::new(arg.span(), "unknown argument of mry")),
+        }
+    }
+    Ok(parsed)
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+    use syn::parse_quote;
+
+    use super::*;
+
+    #[test]
+    fn parses_default() {
+        let args: AttributeArgs = vec![parse_quote!(default = "nice")];
+
+        assert_eq!(
+            parse(args).unwrap().fallback.unwrap().to_string(),
+            quote![mry::Fallback::Nice].to_string()
+        );
+    }
+
+    #[test]
+    fn parses_strict() {
+        let args: AttributeArgs = vec![parse_quote!(strict), parse_quote!(default = "real")];
+
+        let args = parse(args).unwrap();
+        assert!(args.strict);
+        assert!(args.fallback.is_some());
+    }
+
+    #[test]
+    fn no_args() {
+        let args = parse(vec![]).unwrap();
+        assert!(args.fallback.is_none());
+        assert!(!args.strict);
+    }
+
+    #[test]
+    fn unknown_value() {
+        let args: AttributeArgs = vec![parse_quote!(default = "lenient")];
+
+        assert!(parse(args).is_err());
+    }
+
+    #[test]
+    fn unknown_argument() {
+        let args: AttributeArgs = vec![parse_quote!(lenient)];
+
+        assert!(parse(args).is_err());
+    }
+}
@@ -2,10 +2,11 @@ use proc_macro2::{Span, TokenStream};
 use quote::quote;
 use syn::Ident;
 
-use crate::alphabets::alphabets;
+use crate::alphabets::{alphabets, MAX_ARGS};
+use crate::tuple::tuple;
 
 pub fn create() -> TokenStream {
-    let items = alphabets(0..6).map(|args| {
+    let items = alphabets(0..MAX_ARGS + 1).map(|args| {
         let (args, types): (Vec<_>, Vec<_>) = args
             .iter()
             .map(|name| {
@@ -16,16 +17,20 @@ pub fn create() -> TokenStream {
             })
             .unzip();
         let behavior_name = Ident::new(&format!("Behavior{}", args.len()), Span::call_site());
+        let behavior_mut_name =
+            Ident::new(&format!("BehaviorMut{}", args.len()), Span::call_site());
+        let input_type = tuple(&types.iter().map(|ty| quote![#ty]).collect::<Vec<_>>());
+        let input_pat = tuple(&args.iter().map(|arg| quote![#arg]).collect::<Vec<_>>());
         quote! {
             #[doc(hidden)]
             pub struct #behavior_name<I, O>(Box<dyn FnMut(I) -> O + Send + Sync + 'static>);
 
-            impl<Fn, O, #(#types),*> From<Fn> for #behavior_name<(#(#types),*), O>
+            impl<Fn, R, #(#types),*> From<Fn> for #behavior_name<#input_type, R>
             where
-                Fn: FnMut(#(#types),*) -> O + Send + Sync + 'static,
+                Fn: FnMut(#(#types),*) -> R + Send + Sync + 'static,
             {
                 fn from(mut function: Fn) -> Self {
-                    #behavior_name(Box::new(move |(#(#args),*)| function(#(#args),*)))
+                    #behavior_name(Box::new(move |#input_pat| function(#(#args),*)))
                 }
             }
 
@@ -34,6 +39,28 @@ pub fn create() -> TokenStream {
                     Behavior::Function(self.0)
                 }
             }
+
+            impl<I, O> WithMut for #behavior_name<I, O> {
+                type Mut = #behavior_mut_name<I, O>;
+            }
+
+            #[doc(hidden)]
+            pub struct #behavior_mut_name<I, O>(Box<dyn FnMut(&mut I) -> O + Send + Sync + 'static>);
+
+            impl<Fn, R, #(#types),*> From<Fn> for #behavior_mut_name<#input_type, R>
+            where
+                Fn: FnMut(#(&mut #types),*) -> R + Send + Sync + 'static,
+            {
+                fn from(mut function: Fn) -> Self {
+                    #behavior_mut_name(Box::new(move |#input_pat: &mut #input_type| function(#(#args),*)))
+                }
+            }
+
+            impl<I, O> Into<Behavior<I, O>> for #behavior_mut_name<I, O> {
+                fn into(self) -> Behavior<I, O> {
+                    Behavior::FunctionMut(self.0)
+                }
+            }
         }
     });
     quote![#(#items)*]
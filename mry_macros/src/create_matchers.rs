@@ -2,10 +2,11 @@ use proc_macro2::{Span, TokenStream};
 use quote::quote;
 use syn::{Ident, Index};
 
-use crate::alphabets::alphabets;
+use crate::alphabets::{alphabets, MAX_ARGS};
+use crate::tuple::tuple;
 
 pub(crate) fn create() -> TokenStream {
-    let items = alphabets(2..6).map(|args| {
+    let items = alphabets(2..MAX_ARGS + 1).map(|args| {
         let (args, types): (Vec<_>, Vec<_>) = args
             .iter()
             .map(|name| {
@@ -19,26 +20,69 @@ pub(crate) fn create() -> TokenStream {
         let matchers: Vec<_> = types.iter().map(|ty| quote![Matcher<#ty>]).collect();
         let trait_bounds: Vec<_> = types
             .iter()
-            .map(|ty| quote![#ty: PartialEq + Debug + Send + Sync + 'static])
+            .map(|ty| quote![#ty: Debug + Send + Sync + 'static])
             .collect();
+        let input_type = tuple(&types.iter().map(|ty| quote![#ty]).collect::<Vec<_>>());
+        let input_pat = tuple(&args.iter().map(|arg| quote![#arg]).collect::<Vec<_>>());
         let types = quote![#(#types),*];
         let matchers = quote![#(#matchers,)*];
         let matches = args.iter().enumerate().map(|(index, arg)| {
             let index = Index::from(index);
             quote![self.#index.matches(#arg)]
         });
+        let fields = (0..args.len()).map(|index| {
+            let index = Index::from(index);
+            quote![.field(&self.#index)]
+        });
+        let captures = args.iter().enumerate().map(|(index, arg)| {
+            let index = Index::from(index);
+            quote![self.#index.capture(#arg);]
+        });
+        let matches_any = (0..args.len()).map(|index| {
+            let index = Index::from(index);
+            quote![self.#index.matches_any()]
+        });
+        let comparables = (0..args.len()).map(|index| {
+            let index = Index::from(index);
+            quote![self.#index.is_comparable()]
+        });
+        let arg_matches = args.iter().enumerate().map(|(index, arg)| {
+            let index = Index::from(index);
+            quote![ArgMatch::new(&self.#index, #arg)]
+        });
         let args = quote![#(#args),*];
         quote! {
-            #[derive(Debug)]
             struct #matcher_name<#(#trait_bounds),*>(#matchers);
 
-            impl<#(#trait_bounds),*> CompositeMatcher<(#types)> for #matcher_name<#types> {
-                fn matches(&self, (#args): &(#types)) -> bool {
+            impl<#(#trait_bounds),*> Debug for #matcher_name<#types> {
+                fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                    f.debug_tuple("")#(#fields)*.finish()
+                }
+            }
+
+            impl<#(#trait_bounds),*> CompositeMatcher<#input_type> for #matcher_name<#types> {
+                fn matches(&self, #input_pat: &#input_type) -> bool {
                     #(#matches)&&*
                 }
+
+                fn capture(&self, #input_pat: &#input_type) {
+                    #(#captures)*
+                }
+
+                fn matches_any(&self) -> bool {
+                    #(#matches_any)&&*
+                }
+
+                fn is_comparable(&self) -> bool {
+                    #(#comparables)&&*
+                }
+
+                fn match_args(&self, #input_pat: &#input_type) -> Option<Vec<ArgMatch>> {
+                    Some(vec![#(#arg_matches),*])
+                }
             }
 
-            impl<#(#trait_bounds),*> From<(#matchers)> for Matcher<(#types)> {
+            impl<#(#trait_bounds),*> From<(#matchers)> for Matcher<#input_type> {
                 fn from((#args): (#matchers)) -> Self {
                     Matcher::Composite(Box::new(#matcher_name(#args)))
                 }
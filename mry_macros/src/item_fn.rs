@@ -5,11 +5,13 @@ use syn::ItemFn;
 use crate::method;
 
 pub(crate) fn transform(input: ItemFn) -> TokenStream {
-    let (original, mock) = method::transform(
+    let (original, mock, call) = method::transform(
         quote![Box::new(mry::STATIC_MOCKS.write())],
         Default::default(),
         "",
-        quote![mry::STATIC_MOCKS.write().record_call_and_find_mock_output],
+        "",
+        &Default::default(),
+        quote![mry::STATIC_MOCKS.write()],
         Some(&input.vis),
         &input.attrs,
         &input.sig,
@@ -19,6 +21,7 @@ pub(crate) fn transform(input: ItemFn) -> TokenStream {
     quote! {
         #original
         #mock
+        #call
     }
 }
 
@@ -41,24 +44,89 @@ mod test {
         assert_eq!(
             transform(input).to_string(),
             quote! {
+				#[cfg_attr(debug_assertions, track_caller)]
 				fn meow(count: usize) -> String {
-					if let Some(out) = mry::STATIC_MOCKS.write().record_call_and_find_mock_output(std::any::Any::type_id(&meow), "meow", (count.clone())) {
-						return out;
-					}
-					{
-                        "meow".repeat(count)
-                    }
+					let mry_output = mry::STATIC_MOCKS.write().record_call_and_find_real_impl(std::any::Any::type_id(&meow), "meow", (count.clone()), mry::default_output!(String), Some(std::panic::Location::caller()));
+					mry_output.or_real_impl((count), move |#[allow(unused_variables)] (count): (usize)| -> String {
+						{
+							"meow".repeat(count)
+						}
+					})
 				}
 
-				pub fn mock_meow<'mry>(arg0: impl Into<mry::Matcher<usize>>) -> mry::MockLocator<'mry, (usize), String, mry::Behavior1<(usize), String> > {
+				pub fn mock_meow<'mry>(arg0: impl Into<mry::Matcher<usize>>) -> mry::MockLocator<'mry, (usize), String, mry::Behavior1<(usize), String>, MeowCall> {
 					mry::MockLocator {
 						mocks: Box::new(mry::STATIC_MOCKS.write()),
 						key: std::any::Any::type_id(&meow),
 						name: "meow",
+						params: &["count"],
 						matcher: Some((arg0.into(),).into()),
+						options: Default::default(),
 						_phantom: Default::default(),
 					}
 				}
+
+                #[doc = " Arguments of a call to `meow`"]
+                #[allow(dead_code)]
+                #[derive(Debug, Clone)]
+                pub struct MeowCall {
+                    pub count: usize,
+                }
+
+                impl From<(usize)> for MeowCall {
+                    fn from((count): (usize)) -> Self {
+                        Self { count }
+                    }
+                }
+            }
+            .to_string()
+        );
+    }
+    #[test]
+    fn async_fn_awaits_mock_outside_of_lock() {
+        let input: ItemFn = parse2(quote! {
+            async fn meow(count: usize) -> String {
+                "meow".repeat(count)
+            }
+        })
+        .unwrap();
+
+        assert_eq!(
+            transform(input).to_string(),
+            quote! {
+                async fn meow(count: usize) -> String {
+                    let mry_output = mry::STATIC_MOCKS.write().record_call_and_find_real_impl(std::any::Any::type_id(&meow), "meow", (count.clone()), mry::default_output!(String), None);
+                    mry_output.or_real_impl_async((count), move |#[allow(unused_variables)] (count): (usize)| async move {
+                        {
+                            "meow".repeat(count)
+                        }
+                    }).await
+                }
+
+                pub fn mock_meow<'mry>(arg0: impl Into<mry::Matcher<usize>>) -> mry::MockLocator<'mry, (usize), String, mry::Behavior1<(usize), String>, MeowCall> {
+                    mry::MockLocator {
+                        mocks: Box::new(mry::STATIC_MOCKS.write()),
+                        key: std::any::Any::type_id(&meow),
+                        name: "meow",
+                        params: &["count"],
+                        matcher: Some((arg0.into(),).into()),
+                        options: Default::default(),
+                        _phantom: Default::default(),
+                    }
+                }
+
+                #[doc = " Arguments of a call to `meow`"]
+                #[allow(dead_code)]
+                #[derive(Debug, Clone)]
+                pub struct MeowCall {
+                    pub count: usize,
+                }
+
+                impl From<(usize)> for MeowCall {
+                    fn from((count): (usize)) -> Self {
+                        Self { count }
+                    }
+                }
             }
             .to_string()
         );
@@ -3,7 +3,7 @@ use proc_macro2::TokenStream;
 use quote::{quote, ToTokens};
 use syn::visit::Visit;
 use syn::visit_mut::VisitMut;
-use syn::{parse2, FnArg, Ident, ImplItem, ItemImpl, Path};
+use syn::{parse2, FnArg, Ident, ImplItem, ItemImpl, Path, Type};
 
 #[derive(Default)]
 struct TypeParameterVisitor(Vec<String>);
@@ -104,18 +104,26 @@ pub(crate) fn transform(mut input: ItemImpl) -> TokenStream {
         .replace(" >", ">")
         .replace(" <", "<")
         .replace("< ", "<");
+    // `Cat` and `CatAnimal` for `impl Animal for Cat`, used to name call records
+    let call_prefix = match &*input.self_ty {
+        Type::Path(path) => last_ident(&path.path),
+        _ => String::new(),
+    } + &trait_name.map(last_ident).unwrap_or_default();
 
-    let (members, impl_members): (Vec<_>, Vec<_>) = input
-        .items
-        .iter()
-        .map(|item| {
+    let mut members = Vec::new();
+    let mut impl_members = Vec::new();
+    let mut calls = Vec::new();
+    for item in &input.items {
+        let (member, impl_member, call) =
             if let ImplItem::Method(method) = item {
                 if let Some(FnArg::Receiver(_)) = method.sig.inputs.first() {
                     method::transform(
                         quote![self.mry.mocks_write()],
                         quote![#qualified_type::],
                         &(type_name.clone() + "::"),
-                        quote![self.mry.record_call_and_find_mock_output],
+                        &call_prefix,
+                        generics,
+                        quote![self.mry],
                         Some(&method.vis),
                         &method.attrs,
                         &method.sig,
@@ -132,7 +140,9 @@ pub(crate) fn transform(mut input: ItemImpl) -> TokenStream {
                         quote![Box::new(mry::STATIC_MOCKS.write())],
                         quote![#qualified_type::],
                         &(type_name.clone() + "::"),
-                        quote![mry::STATIC_MOCKS.write().record_call_and_find_mock_output],
+                        &call_prefix,
+                        generics,
+                        quote![mry::STATIC_MOCKS.write()],
                         Some(&method.vis),
                         &method.attrs,
                         &method.sig,
@@ -146,10 +156,16 @@ pub(crate) fn transform(mut input: ItemImpl) -> TokenStream {
                     )
                 }
             } else {
-                (item.to_token_stream(), TokenStream::default())
-            }
-        })
-        .unzip();
+                (
+                    item.to_token_stream(),
+                    TokenStream::default(),
+                    TokenStream::default(),
+                )
+            };
+        members.push(member);
+        impl_members.push(impl_member);
+        calls.push(call);
+    }
 
     let impl_generics = if impl_generics.is_empty() {
         TokenStream::default()
@@ -165,9 +181,18 @@ pub(crate) fn transform(mut input: ItemImpl) -> TokenStream {
         impl #impl_generics #struct_type {
             #(#impl_members)*
         }
+
+        #(#calls)*
     }
 }
 
+fn last_ident(path: &Path) -> String {
+    path.segments
+        .last()
+        .map(|segment| segment.ident.to_string())
+        .unwrap_or_default()
+}
+
 #[cfg(test)]
 mod test {
     use pretty_assertions::assert_eq;
@@ -194,25 +219,41 @@ mod test {
                 impl Cat {
                     #[meow]
                     #[meow]
+                    #[cfg_attr(debug_assertions, track_caller)]
                     fn meow(#[a] &self, #[b] count: usize) -> String {
-                        if let Some(out) = self.mry.record_call_and_find_mock_output(std::any::Any::type_id(&Cat::meow), "Cat::meow", (count.clone())) {
-                            return out;
-                        }
-                        "meow".repeat(count)
+                        let mry_output = self.mry.record_call_and_find_real_impl(std::any::Any::type_id(&Cat::meow), "Cat::meow", (count.clone()), mry::default_output!(String), Some(std::panic::Location::caller()));
+                        mry_output.or_real_impl((count), move |#[allow(unused_variables)] (count): (usize)| -> String {
+                            "meow".repeat(count)
+                        })
                     }
                 }
 
                 impl Cat {
-                    pub fn mock_meow<'mry>(&'mry mut self, arg0: impl Into<mry::Matcher<usize>>) -> mry::MockLocator<'mry, (usize), String, mry::Behavior1<(usize), String> > {
+                    pub fn mock_meow<'mry>(&'mry mut self, arg0: impl Into<mry::Matcher<usize>>) -> mry::MockLocator<'mry, (usize), String, mry::Behavior1<(usize), String>, CatMeowCall> {
                         mry::MockLocator {
                             mocks: self.mry.mocks_write(),
                             key: std::any::Any::type_id(&Cat::meow),
                             name: "Cat::meow",
+                            params: &["count"],
                             matcher: Some((arg0.into(),).into()),
+                            options: Default::default(),
                             _phantom: Default::default(),
                         }
                     }
                 }
+
+                #[doc = " Arguments of a call to `Cat::meow`"]
+                #[allow(dead_code)]
+                #[derive(Debug, Clone)]
+                pub struct CatMeowCall {
+                    pub count: usize,
+                }
+
+                impl From<(usize)> for CatMeowCall {
+                    fn from((count): (usize)) -> Self {
+                        Self { count }
+                    }
+                }
             }
             .to_string()
         );
@@ -233,25 +274,41 @@ mod test {
             transform(input).to_string(),
             quote! {
                 impl<'a, A: Clone> Cat<'a, A> {
+                    #[cfg_attr(debug_assertions, track_caller)]
                     fn meow<'a, B>(&'a self, count: usize) -> B {
-                        if let Some(out) = self.mry.record_call_and_find_mock_output(std::any::Any::type_id(&Cat<'a, A>::meow), "Cat<'a, A>::meow", (count.clone())) {
-                            return out;
-                        }
-                        "meow".repeat(count)
+                        let mry_output = self.mry.record_call_and_find_real_impl(std::any::Any::type_id(&Cat<'a, A>::meow), "Cat<'a, A>::meow", (count.clone()), mry::default_output!(B), Some(std::panic::Location::caller()));
+                        mry_output.or_real_impl((count), move |#[allow(unused_variables)] (count): (usize)| -> B {
+                            "meow".repeat(count)
+                        })
                     }
                 }
 
                 impl <'a, A: Clone> Cat<'a, A> {
-                    pub fn mock_meow<'mry>(&'mry mut self, arg0: impl Into<mry::Matcher<usize>>) -> mry::MockLocator<'mry, (usize), B, mry::Behavior1<(usize), B> > {
+                    pub fn mock_meow<'mry>(&'mry mut self, arg0: impl Into<mry::Matcher<usize>>) -> mry::MockLocator<'mry, (usize), B, mry::Behavior1<(usize), B>, CatMeowCall> {
                         mry::MockLocator {
                             mocks: self.mry.mocks_write(),
                             key: std::any::Any::type_id(&Cat<'a, A>::meow),
                             name: "Cat<'a, A>::meow",
+                            params: &["count"],
                             matcher: Some((arg0.into(),).into()),
+                            options: Default::default(),
                             _phantom: Default::default(),
                         }
                     }
                 }
+
+                #[doc = " Arguments of a call to `Cat<'a, A>::meow`"]
+                #[allow(dead_code)]
+                #[derive(Debug, Clone)]
+                pub struct CatMeowCall {
+                    pub count: usize,
+                }
+
+                impl From<(usize)> for CatMeowCall {
+                    fn from((count): (usize)) -> Self {
+                        Self { count }
+                    }
+                }
             }
             .to_string()
         );
@@ -272,25 +329,39 @@ mod test {
             transform(input).to_string(),
             quote! {
                 impl<A: Clone> Animal<A> for Cat {
+                    #[cfg_attr(debug_assertions, track_caller)]
                     fn name(&self, ) -> String {
-                        if let Some(out) = self.mry.record_call_and_find_mock_output(std::any::Any::type_id(&<Cat as Animal<A> >::name), "<Cat as Animal<A>>::name", ()) {
-                            return out;
-                        }
-                        self.name
+                        let mry_output = self.mry.record_call_and_find_real_impl(std::any::Any::type_id(&<Cat as Animal<A> >::name), "<Cat as Animal<A>>::name", (), mry::default_output!(String), Some(std::panic::Location::caller()));
+                        mry_output.or_real_impl((), move |#[allow(unused_variables)] (): ()| -> String {
+                            self.name
+                        })
                     }
                 }
 
                 impl Cat {
-                    pub fn mock_name<'mry>(&'mry mut self,) -> mry::MockLocator<'mry, (), String, mry::Behavior0<(), String> > {
+                    pub fn mock_name<'mry>(&'mry mut self,) -> mry::MockLocator<'mry, (), String, mry::Behavior0<(), String>, CatAnimalNameCall> {
                         mry::MockLocator {
                             mocks: self.mry.mocks_write(),
                             key: std::any::Any::type_id(&< Cat as Animal < A > >::name),
                             name: "<Cat as Animal<A>>::name",
+                            params: &[],
                             matcher: Some(().into()),
+                            options: Default::default(),
                             _phantom: Default::default(),
                         }
                     }
                 }
+
+                #[doc = " Arguments of a call to `<Cat as Animal<A>>::name`"]
+                #[allow(dead_code)]
+                #[derive(Debug, Clone)]
+                pub struct CatAnimalNameCall {}
+
+                impl From<()> for CatAnimalNameCall {
+                    fn from((): ()) -> Self {
+                        Self {}
+                    }
+                }
             }
             .to_string()
         );
@@ -313,25 +384,39 @@ mod test {
             quote! {
                 impl Iterator for Cat {
                     type Item = String;
+                    #[cfg_attr(debug_assertions, track_caller)]
                     fn next(&self, ) -> Option< <Self as Iterator>::Item> {
-                        if let Some(out) = self.mry.record_call_and_find_mock_output(std::any::Any::type_id(&<Cat as Iterator>::next), "<Cat as Iterator>::next", ()) {
-                            return out;
-                        }
-                        Some(self.name)
+                        let mry_output = self.mry.record_call_and_find_real_impl(std::any::Any::type_id(&<Cat as Iterator>::next), "<Cat as Iterator>::next", (), mry::default_output!(Option< <Self as Iterator>::Item>), Some(std::panic::Location::caller()));
+                        mry_output.or_real_impl((), move |#[allow(unused_variables)] (): ()| -> Option< <Self as Iterator>::Item> {
+                            Some(self.name)
+                        })
                     }
                 }
 
                 impl Cat {
-                    pub fn mock_next<'mry>(&'mry mut self,) -> mry::MockLocator<'mry, (), Option< <Self as Iterator>::Item >, mry::Behavior0<(), Option< <Self as Iterator>::Item> > > {
+                    pub fn mock_next<'mry>(&'mry mut self,) -> mry::MockLocator<'mry, (), Option< <Self as Iterator>::Item >, mry::Behavior0<(), Option< <Self as Iterator>::Item> >, CatIteratorNextCall> {
                         mry::MockLocator {
                             mocks: self.mry.mocks_write(),
                             key: std::any::Any::type_id(&<Cat as Iterator>::next),
                             name: "<Cat as Iterator>::next",
+                            params: &[],
                             matcher: Some(().into()),
+                            options: Default::default(),
                             _phantom: Default::default(),
                         }
                     }
                 }
+
+                #[doc = " Arguments of a call to `<Cat as Iterator>::next`"]
+                #[allow(dead_code)]
+                #[derive(Debug, Clone)]
+                pub struct CatIteratorNextCall {}
+
+                impl From<()> for CatIteratorNextCall {
+                    fn from((): ()) -> Self {
+                        Self {}
+                    }
+                }
             }
             .to_string()
         );
@@ -352,25 +437,41 @@ mod test {
             transform(input).to_string(),
             quote! {
                 impl Cat {
+                    #[cfg_attr(debug_assertions, track_caller)]
                     fn meow(count: usize) -> String {
-                        if let Some(out) = mry::STATIC_MOCKS.write().record_call_and_find_mock_output(std::any::Any::type_id(&Cat::meow), "Cat::meow", (count.clone())) {
-                            return out;
-                        }
-                        "meow".repeat(count)
+                        let mry_output = mry::STATIC_MOCKS.write().record_call_and_find_real_impl(std::any::Any::type_id(&Cat::meow), "Cat::meow", (count.clone()), mry::default_output!(String), Some(std::panic::Location::caller()));
+                        mry_output.or_real_impl((count), move |#[allow(unused_variables)] (count): (usize)| -> String {
+                            "meow".repeat(count)
+                        })
                     }
                 }
 
                 impl Cat {
-                    pub fn mock_meow<'mry>(arg0: impl Into<mry::Matcher<usize>>) -> mry::MockLocator<'mry, (usize), String, mry::Behavior1<(usize), String> > {
+                    pub fn mock_meow<'mry>(arg0: impl Into<mry::Matcher<usize>>) -> mry::MockLocator<'mry, (usize), String, mry::Behavior1<(usize), String>, CatMeowCall> {
                         mry::MockLocator {
                             mocks: Box::new(mry::STATIC_MOCKS.write()),
                             key: std::any::Any::type_id(&Cat::meow),
                             name: "Cat::meow",
+                            params: &["count"],
                             matcher: Some((arg0.into(), ).into()),
+                            options: Default::default(),
                             _phantom: Default::default(),
                         }
                     }
                 }
+
+                #[doc = " Arguments of a call to `Cat::meow`"]
+                #[allow(dead_code)]
+                #[derive(Debug, Clone)]
+                pub struct CatMeowCall {
+                    pub count: usize,
+                }
+
+                impl From<(usize)> for CatMeowCall {
+                    fn from((count): (usize)) -> Self {
+                        Self { count }
+                    }
+                }
             }
             .to_string()
         );
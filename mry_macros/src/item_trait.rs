@@ -3,6 +3,7 @@ use quote::{quote, ToTokens};
 use syn::visit::Visit;
 use syn::{Ident, ItemTrait};
 
+use crate::args::Args;
 use crate::method;
 
 #[derive(Default)]
@@ -16,7 +17,8 @@ impl<'ast> Visit<'ast> for AsyncTraitFindVisitor {
     }
 }
 
-pub(crate) fn transform(input: ItemTrait) -> TokenStream {
+/// `args` configures the fallback and strictness of the mock object created by `Default`.
+pub(crate) fn transform(input: ItemTrait, args: Args) -> TokenStream {
     let mut async_trait_finder = AsyncTraitFindVisitor::default();
     async_trait_finder.visit_item_trait(&input);
     let async_trait_or_blank = if async_trait_finder.0 {
@@ -30,15 +32,18 @@ pub(crate) fn transform(input: ItemTrait) -> TokenStream {
     let mry_ident = Ident::new(&format!("Mock{}", &input.ident), Span::call_site());
     let vis = &input.vis;
     let panic_message = format!("mock not found for {}", trait_ident);
-    let (items, impl_items): (Vec<_>, Vec<_>) = input
-        .items
-        .iter()
-        .map(|item| match item {
+    let mut items = Vec::new();
+    let mut impl_items = Vec::new();
+    let mut calls = Vec::new();
+    for item in &input.items {
+        let (item, impl_item, call) = match item {
             syn::TraitItem::Method(method) => method::transform(
                 quote![self.mry.mocks_write()],
                 quote![#mry_ident::],
                 &(trait_ident.to_string() + "::"),
-                quote![self.mry.record_call_and_find_mock_output],
+                &trait_ident.to_string(),
+                generics,
+                quote![self.mry],
                 None,
                 &method.attrs,
                 &method.sig,
@@ -49,17 +54,44 @@ pub(crate) fn transform(input: ItemTrait) -> TokenStream {
                     .unwrap_or(quote![panic!(#panic_message)]),
             ),
             _item => todo!(),
-        })
-        .unzip();
+        };
+        items.push(item);
+        impl_items.push(impl_item);
+        calls.push(call);
+    }
+
+    let (derive, default) = if args.fallback.is_some() || args.strict {
+        let fallback = args
+            .fallback
+            .map(|fallback| quote![mry.fallback(#fallback);]);
+        let strict = args.strict.then(|| quote![mry.strict();]);
+        (
+            quote![#[derive(Clone)]],
+            quote! {
+                impl Default for #mry_ident {
+                    fn default() -> Self {
+                        let mut mry = mry::Mry::default();
+                        #fallback
+                        #strict
+                        Self { mry }
+                    }
+                }
+            },
+        )
+    } else {
+        (quote![#[derive(Default, Clone)]], TokenStream::default())
+    };
 
     quote! {
         #input
 
-        #[derive(Default, Clone)]
+        #derive
         #vis struct #mry_ident {
             pub mry: mry::Mry,
         }
 
+        #default
+
         #async_trait_or_blank
         impl #generics #trait_ident for #mry_ident {
             #(#items)*
@@ -68,6 +100,8 @@ pub(crate) fn transform(input: ItemTrait) -> TokenStream {
         impl #mry_ident {
             #(#impl_items)*
         }
+
+        #(#calls)*
     }
 }
 
@@ -88,7 +122,7 @@ mod test {
         .unwrap();
 
         assert_eq!(
-            transform(input).to_string(),
+            transform(input, Args::default()).to_string(),
             quote! {
 				trait Cat {
 					fn meow(&self, count: usize) -> String;
@@ -100,25 +134,41 @@ mod test {
 				}
 
                 impl Cat for MockCat {
+                    #[cfg_attr(debug_assertions, track_caller)]
                     fn meow(&self, count: usize) -> String {
-                        if let Some(out) = self.mry.record_call_and_find_mock_output(std::any::Any::type_id(&MockCat::meow), "Cat::meow", (count.clone())) {
-                            return out;
-                        }
-                        panic!("mock not found for Cat")
+                        let mry_output = self.mry.record_call_and_find_real_impl(std::any::Any::type_id(&MockCat::meow), "Cat::meow", (count.clone()), mry::default_output!(String), Some(std::panic::Location::caller()));
+                        mry_output.or_real_impl((count), move |#[allow(unused_variables)] (count): (usize)| -> String {
+                            panic!("mock not found for Cat")
+                        })
                     }
                 }
 
                 impl MockCat {
-                    pub fn mock_meow<'mry>(&'mry mut self, arg0: impl Into<mry::Matcher<usize>>) -> mry::MockLocator<'mry, (usize), String, mry::Behavior1<(usize), String> > {
+                    pub fn mock_meow<'mry>(&'mry mut self, arg0: impl Into<mry::Matcher<usize>>) -> mry::MockLocator<'mry, (usize), String, mry::Behavior1<(usize), String>, CatMeowCall> {
                         mry::MockLocator {
                             mocks: self.mry.mocks_write(),
                             key: std::any::Any::type_id(&MockCat::meow),
                             name: "Cat::meow",
+                            params: &["count"],
                             matcher: Some((arg0.into(),).into()),
+                            options: Default::default(),
                             _phantom: Default::default(),
                         }
                     }
                 }
+
+                #[doc = " Arguments of a call to `Cat::meow`"]
+                #[allow(dead_code)]
+                #[derive(Debug, Clone)]
+                pub struct CatMeowCall {
+                    pub count: usize,
+                }
+
+                impl From<(usize)> for CatMeowCall {
+                    fn from((count): (usize)) -> Self {
+                        Self { count }
+                    }
+                }
             }
             .to_string()
         );
@@ -134,7 +184,7 @@ mod test {
         .unwrap();
 
         assert_eq!(
-            transform(input).to_string(),
+            transform(input, Args::default()).to_string(),
             quote! {
 				pub trait Cat {
 					fn meow(&self, count: usize) -> String;
@@ -146,25 +196,41 @@ mod test {
 				}
 
                 impl Cat for MockCat {
+                    #[cfg_attr(debug_assertions, track_caller)]
                     fn meow(&self, count: usize) -> String {
-                        if let Some(out) = self.mry.record_call_and_find_mock_output(std::any::Any::type_id(&MockCat::meow), "Cat::meow", (count.clone())) {
-                            return out;
-                        }
-                        panic!("mock not found for Cat")
+                        let mry_output = self.mry.record_call_and_find_real_impl(std::any::Any::type_id(&MockCat::meow), "Cat::meow", (count.clone()), mry::default_output!(String), Some(std::panic::Location::caller()));
+                        mry_output.or_real_impl((count), move |#[allow(unused_variables)] (count): (usize)| -> String {
+                            panic!("mock not found for Cat")
+                        })
                     }
                 }
 
                 impl MockCat {
-                    pub fn mock_meow<'mry>(&'mry mut self, arg0: impl Into<mry::Matcher<usize>>) -> mry::MockLocator<'mry, (usize), String, mry::Behavior1<(usize), String> > {
+                    pub fn mock_meow<'mry>(&'mry mut self, arg0: impl Into<mry::Matcher<usize>>) -> mry::MockLocator<'mry, (usize), String, mry::Behavior1<(usize), String>, CatMeowCall> {
                         mry::MockLocator {
                             mocks: self.mry.mocks_write(),
                             key: std::any::Any::type_id(&MockCat::meow),
                             name: "Cat::meow",
+                            params: &["count"],
                             matcher: Some((arg0.into(),).into()),
+                            options: Default::default(),
                             _phantom: Default::default(),
                         }
                     }
                 }
+
+                #[doc = " Arguments of a call to `Cat::meow`"]
+                #[allow(dead_code)]
+                #[derive(Debug, Clone)]
+                pub struct CatMeowCall {
+                    pub count: usize,
+                }
+
+                impl From<(usize)> for CatMeowCall {
+                    fn from((count): (usize)) -> Self {
+                        Self { count }
+                    }
+                }
             }
             .to_string()
         );
@@ -181,7 +247,7 @@ mod test {
         .unwrap();
 
         assert_eq!(
-            transform(input).to_string(),
+            transform(input, Args::default()).to_string(),
             quote! {
                 #[async_trait::async_trait]
 				trait Cat {
@@ -196,26 +262,150 @@ mod test {
                 #[async_trait::async_trait]
                 impl Cat for MockCat {
                     async fn meow(&self, count: usize) -> String {
-                        if let Some(out) = self.mry.record_call_and_find_mock_output(std::any::Any::type_id(&MockCat::meow), "Cat::meow", (count.clone())) {
-                            return out;
+                        let mry_output = self.mry.record_call_and_find_real_impl(std::any::Any::type_id(&MockCat::meow), "Cat::meow", (count.clone()), mry::default_output!(String), None);
+                        mry_output.or_real_impl_async((count), move |#[allow(unused_variables)] (count): (usize)| async move {
+                            panic!("mock not found for Cat")
+                        }).await
+                    }
+                }
+
+                impl MockCat {
+                    pub fn mock_meow<'mry>(&'mry mut self, arg0: impl Into<mry::Matcher<usize>>) -> mry::MockLocator<'mry, (usize), String, mry::Behavior1<(usize), String>, CatMeowCall> {
+                        mry::MockLocator {
+                            mocks: self.mry.mocks_write(),
+                            key: std::any::Any::type_id(&MockCat::meow),
+                            name: "Cat::meow",
+                            params: &["count"],
+                            matcher: Some((arg0.into(),).into()),
+                            options: Default::default(),
+                            _phantom: Default::default(),
                         }
-                        panic!("mock not found for Cat")
+                    }
+                }
+
+                #[doc = " Arguments of a call to `Cat::meow`"]
+                #[allow(dead_code)]
+                #[derive(Debug, Clone)]
+                pub struct CatMeowCall {
+                    pub count: usize,
+                }
+
+                impl From<(usize)> for CatMeowCall {
+                    fn from((count): (usize)) -> Self {
+                        Self { count }
+                    }
+                }
+            }
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn default_with_fallback() {
+        let input: ItemTrait = parse2(quote! {
+            trait Cat {
+                fn meow(&self, count: usize) -> String;
+            }
+        })
+        .unwrap();
+
+        assert_eq!(
+            transform(
+                input,
+                Args {
+                    fallback: Some(quote![mry::Fallback::Nice]),
+                    strict: false,
+                }
+            ).to_string(),
+            quote! {
+                trait Cat {
+                    fn meow(&self, count: usize) -> String;
+                }
+
+                #[derive(Clone)]
+                struct MockCat {
+                    pub mry : mry::Mry,
+                }
+
+                impl Default for MockCat {
+                    fn default() -> Self {
+                        let mut mry = mry::Mry::default();
+                        mry.fallback(mry::Fallback::Nice);
+                        Self { mry }
+                    }
+                }
+
+                impl Cat for MockCat {
+                    #[cfg_attr(debug_assertions, track_caller)]
+                    fn meow(&self, count: usize) -> String {
+                        let mry_output = self.mry.record_call_and_find_real_impl(std::any::Any::type_id(&MockCat::meow), "Cat::meow", (count.clone()), mry::default_output!(String), Some(std::panic::Location::caller()));
+                        mry_output.or_real_impl((count), move |#[allow(unused_variables)] (count): (usize)| -> String {
+                            panic!("mock not found for Cat")
+                        })
                     }
                 }
 
                 impl MockCat {
-                    pub fn mock_meow<'mry>(&'mry mut self, arg0: impl Into<mry::Matcher<usize>>) -> mry::MockLocator<'mry, (usize), String, mry::Behavior1<(usize), String> > {
+                    pub fn mock_meow<'mry>(&'mry mut self, arg0: impl Into<mry::Matcher<usize>>) -> mry::MockLocator<'mry, (usize), String, mry::Behavior1<(usize), String>, CatMeowCall> {
                         mry::MockLocator {
                             mocks: self.mry.mocks_write(),
                             key: std::any::Any::type_id(&MockCat::meow),
                             name: "Cat::meow",
+                            params: &["count"],
                             matcher: Some((arg0.into(),).into()),
+                            options: Default::default(),
                             _phantom: Default::default(),
                         }
                     }
                 }
+
+                #[doc = " Arguments of a call to `Cat::meow`"]
+                #[allow(dead_code)]
+                #[derive(Debug, Clone)]
+                pub struct CatMeowCall {
+                    pub count: usize,
+                }
+
+                impl From<(usize)> for CatMeowCall {
+                    fn from((count): (usize)) -> Self {
+                        Self { count }
+                    }
+                }
             }
             .to_string()
         );
     }
+
+    #[test]
+    fn default_with_strict() {
+        let input: ItemTrait = parse2(quote! {
+            trait Cat {
+                fn meow(&self, count: usize) -> String;
+            }
+        })
+        .unwrap();
+
+        let output = transform(
+            input,
+            Args {
+                fallback: None,
+                strict: true,
+            },
+        )
+        .to_string();
+
+        assert!(output.contains(
+            &quote! {
+                impl Default for MockCat {
+                    fn default() -> Self {
+                        let mut mry = mry::Mry::default();
+                        mry.strict();
+                        Self { mry }
+                    }
+                }
+            }
+            .to_string()
+        ));
+        assert!(!output.contains("derive (Default"));
+    }
 }
@@ -1,13 +1,16 @@
+mod args;
 mod create_behaviors;
 mod create_matchers;
 mod item_fn;
 mod item_impl;
 mod item_struct;
 mod item_trait;
+mod lock;
 mod method;
 mod new;
-mod lock;
-use proc_macro2::TokenStream;
+mod pat;
+mod tuple;
+use proc_macro2::{Span, TokenStream};
 use quote::ToTokens;
 use syn::visit_mut::VisitMut;
 mod alphabets;
@@ -24,18 +27,37 @@ enum Target {
 }
 
 #[proc_macro_attribute]
-pub fn mry(_: proc_macro::TokenStream, input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+pub fn mry(
+    attribute: proc_macro::TokenStream,
+    input: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    let args = match args::parse(parse_macro_input!(attribute as AttributeArgs)) {
+        Ok(args) => args,
+        Err(err) => return err.to_compile_error().into(),
+    };
     match parse(input.clone())
         .map(Target::ItemStruct)
         .or_else(|_| parse(input.clone()).map(Target::ItemImpl))
         .or_else(|_| parse(input.clone()).map(Target::ItemTrait))
         .or_else(|_| parse(input.clone()).map(Target::ItemFn))
     {
+        Ok(Target::ItemTrait(target)) => item_trait::transform(target, args).into(),
+        Ok(_) if args.fallback.is_some() => {
+            syn::Error::new(Span::call_site(), "`default` is only supported for traits")
+                .to_compile_error()
+                .into()
+        }
+        Ok(_) if args.strict => syn::Error::new(
+            Span::call_site(),
+            "`strict` is only supported for traits, use `mry.strict()` for structs",
+        )
+        .to_compile_error()
+        .into(),
         Ok(target) => {
             let token_stream = match target {
                 Target::ItemStruct(target) => item_struct::transform(target),
                 Target::ItemImpl(target) => item_impl::transform(target),
-                Target::ItemTrait(target) => item_trait::transform(target),
+                Target::ItemTrait(target) => item_trait::transform(target, Default::default()),
                 Target::ItemFn(target) => item_fn::transform(target),
             };
             token_stream.into()
@@ -49,6 +71,11 @@ pub fn new(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     new::transform(parse_macro_input!(input as ExprStruct)).into()
 }
 
+#[proc_macro]
+pub fn spy(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    new::transform_spy(parse_macro_input!(input as ExprStruct)).into()
+}
+
 #[proc_macro]
 pub fn create_behaviors(_: proc_macro::TokenStream) -> proc_macro::TokenStream {
     create_behaviors::create().into()
@@ -59,6 +86,13 @@ pub fn create_matchers(_: proc_macro::TokenStream) -> proc_macro::TokenStream {
     create_matchers::create().into()
 }
 
+/// Matches arguments by a pattern with an optional guard, like `pat!(Some(Order { name, .. }) if name.starts_with("a"))`.
+/// The argument is matched by reference, so bindings in the guard are references.
+#[proc_macro]
+pub fn pat(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    pat::transform(input.into()).into()
+}
+
 #[proc_macro_attribute]
 pub fn lock(
     attribute: proc_macro::TokenStream,
@@ -75,7 +109,7 @@ struct M(TokenStream);
 
 impl VisitMut for M {
     fn visit_item_trait_mut(&mut self, i: &mut ItemTrait) {
-        item_trait::transform(i.clone()).to_tokens(&mut self.0)
+        item_trait::transform(i.clone(), Default::default()).to_tokens(&mut self.0)
     }
     fn visit_item_struct_mut(&mut self, i: &mut ItemStruct) {
         item_struct::transform(i.clone()).to_tokens(&mut self.0)
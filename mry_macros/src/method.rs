@@ -1,17 +1,28 @@
 use proc_macro2::{Span, TokenStream};
-use quote::quote;
-use syn::{Attribute, FnArg, Ident, Pat, PatIdent, ReturnType, Signature, Type, Visibility};
+use quote::{quote, quote_spanned};
+use syn::spanned::Spanned;
+use syn::visit::Visit;
+use syn::{
+    parse2, Attribute, FnArg, GenericParam, Generics, Ident, Pat, PatIdent, ReturnType, Signature,
+    Type, Visibility,
+};
 
+use crate::alphabets::MAX_ARGS;
+use crate::tuple::tuple;
+
+/// Returns the method, the mock method, and the call record struct placed outside of the impl
 pub fn transform(
     mocks_write_lock: TokenStream, // `MOCKS.write()`
     method_prefix: TokenStream,    // `Self::`
     method_debug_prefix: &str,     // "Cat::"
-    record_call_and_find_mock_output: TokenStream,
+    call_prefix: &str,             // "Cat"
+    outer_generics: &Generics,     // generics of the impl or the trait
+    mocks: TokenStream,            // `self.mry`
     vis: Option<&Visibility>,
     attrs: &Vec<Attribute>,
     sig: &Signature,
     body: &TokenStream,
-) -> (TokenStream, TokenStream) {
+) -> (TokenStream, TokenStream, TokenStream) {
     // Split into receiver and other inputs
     let mut receiver = TokenStream::default();
     let mut mock_receiver = TokenStream::default();
@@ -32,6 +43,26 @@ pub fn transform(
             }
         })
         .collect();
+    if inputs_without_receiver.len() > MAX_ARGS {
+        let message = format!(
+            "mry supports up to {} arguments, but `{}` has {}",
+            MAX_ARGS,
+            sig.ident,
+            inputs_without_receiver.len()
+        );
+        let error = quote_spanned![sig.inputs.span()=> compile_error!(#message);];
+        return (
+            quote! {
+                #error
+                #(#attrs)*
+                #vis #sig {
+                    #body
+                }
+            },
+            TokenStream::default(),
+            TokenStream::default(),
+        );
+    }
     let mut bindings = Vec::new();
 
     let args_without_receiver: Vec<_> = inputs_without_receiver
@@ -61,13 +92,23 @@ pub fn transform(
         .iter()
         .map(|input| deref_type(&*input.ty))
         .collect();
+    let arg_idents: Vec<_> = args_without_receiver
+        .iter()
+        .map(|input| match &*input.pat {
+            Pat::Ident(pat) => pat.ident.clone(),
+            _ => unreachable!("patterns are replaced with identifiers"),
+        })
+        .collect();
     let cloned_input: Vec<_> = args_without_receiver
         .iter()
-        .map(|input| {
-            let pat = &input.pat;
+        .zip(&arg_idents)
+        .map(|(input, pat)| {
             if is_str(&input.ty) {
                 return quote!(#pat.to_string());
             }
+            if slice_elem(&input.ty).is_some() {
+                return quote!(#pat.to_vec());
+            }
             quote!(#pat.clone())
         })
         .collect();
@@ -83,8 +124,8 @@ pub fn transform(
     let vis = &vis;
     let name = format!("{}{}", method_debug_prefix, ident.to_string());
     let args = quote!(#receiver#(#args_without_receiver),*);
-    let input_type_tuple = quote!((#(#derefed_input_type_tuple),*));
-    let cloned_input_tuple = quote!((#(#cloned_input),*));
+    let input_type_tuple = tuple(&derefed_input_type_tuple);
+    let cloned_input_tuple = tuple(&cloned_input);
     let bindings = bindings.iter().map(|(pat, arg)| quote![let #pat = #arg;]);
     let behavior_name = Ident::new(
         &format!("Behavior{}", inputs_without_receiver.len()),
@@ -103,35 +144,283 @@ pub fn transform(
         })
         .unzip();
     let key = quote![std::any::Any::type_id(&#method_prefix#ident)];
-    (
+    let params: Vec<_> = arg_idents.iter().map(|ident| ident.to_string()).collect();
+    // Call records are tuples if the arguments can't be named outside of the impl
+    let generic_params: Vec<_> = outer_generics
+        .params
+        .iter()
+        .chain(&sig.generics.params)
+        .map(generic_param_name)
+        .collect();
+    let nameable = derefed_input_type_tuple.iter().all(
+        |ty| matches!(parse2::<Type>(ty.clone()), Ok(ty) if is_nameable(&ty, &generic_params)),
+    );
+    let (call_struct, call_type) = if nameable {
+        let call_ident = Ident::new(
+            &format!(
+                "{}{}Call",
+                call_prefix,
+                upper_camel_case(&ident.to_string())
+            ),
+            Span::call_site(),
+        );
+        let doc = format!(" Arguments of a call to `{}`", name);
+        let field_tuple = tuple(
+            &arg_idents
+                .iter()
+                .map(|ident| quote![#ident])
+                .collect::<Vec<_>>(),
+        );
+        (
+            quote! {
+                #[doc = #doc]
+                #[allow(dead_code)]
+                #[derive(Debug, Clone)]
+                pub struct #call_ident {
+                    #(pub #arg_idents: #derefed_input_type_tuple,)*
+                }
+
+                impl From<#input_type_tuple> for #call_ident {
+                    fn from(#field_tuple: #input_type_tuple) -> Self {
+                        Self { #(#arg_idents),* }
+                    }
+                }
+            },
+            quote![, #call_ident],
+        )
+    } else {
+        (TokenStream::default(), TokenStream::default())
+    };
+    let default_output = quote![mry::default_output!(#output_type)];
+    let allow_too_many_arguments = if inputs_without_receiver.len() > 6 {
+        quote![#[allow(clippy::too_many_arguments)]]
+    } else {
+        TokenStream::default()
+    };
+    // Values of `&mut` arguments possibly modified by the mock.
+    // `mry_` prefixed names avoid shadowing the arguments in writing back.
+    let (written, write_back): (Vec<_>, Vec<_>) = args_without_receiver
+        .iter()
+        .enumerate()
+        .map(|(i, input)| {
+            let pat = &input.pat;
+            let value = Ident::new(&format!("mry_written{}", i), Span::call_site());
+            match &*input.ty {
+                Type::Reference(ty) if ty.mutability.is_some() => {
+                    let write_back = if slice_elem(&input.ty).is_some() {
+                        quote! {
+                            for (to, from) in #pat.iter_mut().zip(#value) {
+                                *to = from;
+                            }
+                        }
+                    } else {
+                        quote![*#pat = #value;]
+                    };
+                    (quote![#value], write_back)
+                }
+                _ => (quote![_], TokenStream::default()),
+            }
+        })
+        .unzip();
+    let has_mut_args = write_back.iter().any(|write_back| !write_back.is_empty());
+    let written = tuple(&written);
+    // Failures of the mock are reported where the method is called.
+    // `track_caller` doesn't work for async functions.
+    let (track_caller, location) = if asyn.is_some() {
+        (TokenStream::default(), quote![None])
+    } else if attrs.iter().any(|attr| attr.path.is_ident("track_caller")) {
+        (
+            TokenStream::default(),
+            quote![Some(std::panic::Location::caller())],
+        )
+    } else {
+        (
+            quote![#[cfg_attr(debug_assertions, track_caller)]],
+            quote![Some(std::panic::Location::caller())],
+        )
+    };
+    // The output of async mocks is a future awaited outside of the lock of mocks
+    let find_mock_output = if has_mut_args && asyn.is_some() {
+        quote! {
+            let mut mry_input = #cloned_input_tuple;
+            let mry_out = #mocks.record_call_and_find_mock_future_mut(#key, #name, &mut mry_input, #default_output, None);
+            if let Some(mry_out) = mry_out {
+                let #written = mry_input;
+                #(#write_back)*
+                return mry_out.await;
+            }
+        }
+    } else if has_mut_args {
+        quote! {
+            let mut mry_input = #cloned_input_tuple;
+            if let Some(mry_out) = #mocks.record_call_and_find_mock_output_mut(#key, #name, &mut mry_input, #default_output, #location) {
+                let #written = mry_input;
+                #(#write_back)*
+                return mry_out;
+            }
+        }
+    } else if asyn.is_some() {
+        quote! {
+            let out = #mocks.record_call_and_find_mock_future(#key, #name, #cloned_input_tuple, #default_output, None);
+            if let Some(out) = out {
+                return out.await;
+            }
+        }
+    } else {
+        quote! {
+            if let Some(out) = #mocks.record_call_and_find_mock_output(#key, #name, #cloned_input_tuple, #default_output, #location) {
+                return out;
+            }
+        }
+    };
+    // Without references in the arguments, the real implementation is made a closure taking the input,
+    // so mocks can call it with other arguments or wrap it.
+    let has_ref_args = args_without_receiver
+        .iter()
+        .any(|input| matches!(&*input.ty, Type::Reference(_)));
+    let original = if has_ref_args {
         quote! {
             #(#attrs)*
+            #track_caller
             #vis #asyn fn #ident #generics(#args) -> #output_type {
-                if let Some(out) = #record_call_and_find_mock_output(#key, #name, #cloned_input_tuple) {
-                    return out;
-                }
+                #find_mock_output
                 #(#bindings)*
                 #body
             }
-        },
+        }
+    } else {
+        // `mut` of the arguments moves to the closure
+        let args_without_mut =
+            args_without_receiver
+                .iter()
+                .zip(&arg_idents)
+                .map(|(input, ident)| {
+                    let attrs = &input.attrs;
+                    let ty = &input.ty;
+                    quote![#(#attrs)* #ident: #ty]
+                });
+        let pats: Vec<_> = args_without_receiver
+            .iter()
+            .map(|input| {
+                let pat = &input.pat;
+                quote![#pat]
+            })
+            .collect();
+        let pat_tuple = tuple(&pats);
+        let input_tuple = tuple(
+            &arg_idents
+                .iter()
+                .map(|ident| quote![#ident])
+                .collect::<Vec<_>>(),
+        );
+        let call_real_impl = if asyn.is_some() {
+            quote! {
+                mry_output.or_real_impl_async(#input_tuple, move |#[allow(unused_variables)] #pat_tuple: #input_type_tuple| async move {
+                    #(#bindings)*
+                    #body
+                }).await
+            }
+        } else {
+            quote! {
+                mry_output.or_real_impl(#input_tuple, move |#[allow(unused_variables)] #pat_tuple: #input_type_tuple| -> #output_type {
+                    #(#bindings)*
+                    #body
+                })
+            }
+        };
+        quote! {
+            #(#attrs)*
+            #track_caller
+            #vis #asyn fn #ident #generics(#receiver #(#args_without_mut),*) -> #output_type {
+                let mry_output = #mocks.record_call_and_find_real_impl(#key, #name, #cloned_input_tuple, #default_output, #location);
+                #call_real_impl
+            }
+        }
+    };
+    (
+        original,
         quote! {
-            pub fn #mock_ident<'mry>(#mock_receiver#(#mock_args),*) -> mry::MockLocator<'mry, #input_type_tuple, #output_type, #behavior_type> {
+            #allow_too_many_arguments
+            pub fn #mock_ident<'mry>(#mock_receiver#(#mock_args),*) -> mry::MockLocator<'mry, #input_type_tuple, #output_type, #behavior_type #call_type> {
                 mry::MockLocator {
                     mocks: #mocks_write_lock,
                     key: #key,
                     name: #name,
+                    params: &[#(#params),*],
                     matcher: Some((#(#mock_args_into,)*).into()),
+                    options: Default::default(),
                     _phantom: Default::default(),
                 }
             }
         },
+        call_struct,
     )
 }
 
+/// Finds what can't be named outside of the impl, such as generic parameters and `Self`
+struct UnnameableFinder<'a> {
+    generic_params: &'a [String],
+    found: bool,
+}
+
+impl<'a, 'ast> Visit<'ast> for UnnameableFinder<'a> {
+    fn visit_path_segment(&mut self, segment: &'ast syn::PathSegment) {
+        let ident = segment.ident.to_string();
+        if ident == "Self" || self.generic_params.contains(&ident) {
+            self.found = true;
+        }
+        syn::visit::visit_path_segment(self, segment);
+    }
+    fn visit_lifetime(&mut self, lifetime: &'ast syn::Lifetime) {
+        let ident = lifetime.ident.to_string();
+        if ident == "_" || self.generic_params.contains(&ident) {
+            self.found = true;
+        }
+    }
+    fn visit_type_impl_trait(&mut self, _: &'ast syn::TypeImplTrait) {
+        self.found = true;
+    }
+}
+
+fn is_nameable(ty: &Type, generic_params: &[String]) -> bool {
+    let mut finder = UnnameableFinder {
+        generic_params,
+        found: false,
+    };
+    finder.visit_type(ty);
+    !finder.found
+}
+
+fn generic_param_name(param: &GenericParam) -> String {
+    match param {
+        GenericParam::Type(ty) => ty.ident.to_string(),
+        GenericParam::Lifetime(lifetime) => lifetime.lifetime.ident.to_string(),
+        GenericParam::Const(cons) => cons.ident.to_string(),
+    }
+}
+
+/// `meow_loudly` to `MeowLoudly`
+fn upper_camel_case(name: &str) -> String {
+    name.trim_start_matches("r#")
+        .split('_')
+        .map(|word| {
+            let mut chars = word.chars();
+            chars
+                .next()
+                .map(|first| first.to_uppercase().chain(chars).collect())
+                .unwrap_or_default()
+        })
+        .collect::<Vec<String>>()
+        .concat()
+}
+
 pub fn deref_type(ty: &Type) -> TokenStream {
     if is_str(&ty) {
         return quote!(String);
     }
+    if let Some(elem) = slice_elem(ty) {
+        return quote!(Vec<#elem>);
+    }
     match &ty {
         Type::Reference(ty) => {
             let ty = &ty.elem;
@@ -141,6 +430,17 @@ pub fn deref_type(ty: &Type) -> TokenStream {
     }
 }
 
+/// Returns the element type if the type is a reference to a slice
+pub fn slice_elem(ty: &Type) -> Option<&Type> {
+    match ty {
+        Type::Reference(ty) => match &*ty.elem {
+            Type::Slice(slice) => Some(&slice.elem),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
 pub fn is_str(ty: &Type) -> bool {
     match ty {
         Type::Reference(ty) => {
@@ -166,21 +466,23 @@ mod test {
         fn to_string(&self) -> String;
     }
 
-    impl ToString for (TokenStream, TokenStream) {
+    impl ToString for (TokenStream, TokenStream, TokenStream) {
         fn to_string(&self) -> String {
-            (self.0.to_string() + " " + &self.1.to_string())
+            (self.0.to_string() + " " + &self.1.to_string() + " " + &self.2.to_string())
                 .to_string()
                 .trim()
                 .to_string()
         }
     }
 
-    fn t(method: &ImplItemMethod) -> (TokenStream, TokenStream) {
+    fn t(method: &ImplItemMethod) -> (TokenStream, TokenStream, TokenStream) {
         transform(
             quote![self.mry.mocks_write()],
             quote![Self::],
             "Cat::",
-            quote![self.mry.record_call_and_find_mock_output],
+            "Cat",
+            &Default::default(),
+            quote![self.mry],
             Some(&method.vis),
             &method.attrs,
             &method.sig,
@@ -207,22 +509,38 @@ mod test {
         assert_eq!(
             t(&input).to_string(),
             quote! {
+                #[cfg_attr(debug_assertions, track_caller)]
                 fn meow(&self, count: usize) -> String {
-                    if let Some(out) = self.mry.record_call_and_find_mock_output(std::any::Any::type_id(&Self::meow), "Cat::meow", (count.clone())) {
-                        return out;
-                    }
-                    "meow".repeat(count)
+                    let mry_output = self.mry.record_call_and_find_real_impl(std::any::Any::type_id(&Self::meow), "Cat::meow", (count.clone()), mry::default_output!(String), Some(std::panic::Location::caller()));
+                    mry_output.or_real_impl((count), move |#[allow(unused_variables)] (count): (usize)| -> String {
+                        "meow".repeat(count)
+                    })
                 }
 
-                pub fn mock_meow<'mry>(&'mry mut self, arg0: impl Into<mry::Matcher<usize>>) -> mry::MockLocator<'mry, (usize), String, mry::Behavior1<(usize), String> > {
+                pub fn mock_meow<'mry>(&'mry mut self, arg0: impl Into<mry::Matcher<usize>>) -> mry::MockLocator<'mry, (usize), String, mry::Behavior1<(usize), String>, CatMeowCall> {
                     mry::MockLocator {
                         mocks: self.mry.mocks_write(),
                         key: std::any::Any::type_id(&Self::meow),
                         name: "Cat::meow",
+                        params: &["count"],
                         matcher: Some((arg0.into(),).into()),
+                        options: Default::default(),
                         _phantom: Default::default(),
                     }
                 }
+
+                #[doc = " Arguments of a call to `Cat::meow`"]
+                #[allow(dead_code)]
+                #[derive(Debug, Clone)]
+                pub struct CatMeowCall {
+                    pub count: usize,
+                }
+
+                impl From<(usize)> for CatMeowCall {
+                    fn from((count): (usize)) -> Self {
+                        Self { count }
+                    }
+                }
             }
             .to_string()
         );
@@ -240,22 +558,36 @@ mod test {
         assert_eq!(
             t(&input).to_string(),
             quote! {
+                #[cfg_attr(debug_assertions, track_caller)]
                 fn meow(&self, ) -> String {
-                    if let Some(out) = self.mry.record_call_and_find_mock_output(std::any::Any::type_id(&Self::meow), "Cat::meow", ()) {
-                        return out;
-                    }
-                    "meow".into()
+                    let mry_output = self.mry.record_call_and_find_real_impl(std::any::Any::type_id(&Self::meow), "Cat::meow", (), mry::default_output!(String), Some(std::panic::Location::caller()));
+                    mry_output.or_real_impl((), move |#[allow(unused_variables)] (): ()| -> String {
+                        "meow".into()
+                    })
                 }
 
-                pub fn mock_meow<'mry>(&'mry mut self, ) -> mry::MockLocator<'mry, (), String, mry::Behavior0<(), String> > {
+                pub fn mock_meow<'mry>(&'mry mut self, ) -> mry::MockLocator<'mry, (), String, mry::Behavior0<(), String>, CatMeowCall> {
                     mry::MockLocator {
                         mocks: self.mry.mocks_write(),
                         key: std::any::Any::type_id(&Self::meow),
                         name: "Cat::meow",
+                        params: &[],
                         matcher: Some(().into()),
+                        options: Default::default(),
                         _phantom: Default::default(),
                     }
                 }
+
+                #[doc = " Arguments of a call to `Cat::meow`"]
+                #[allow(dead_code)]
+                #[derive(Debug, Clone)]
+                pub struct CatMeowCall {}
+
+                impl From<()> for CatMeowCall {
+                    fn from((): ()) -> Self {
+                        Self {}
+                    }
+                }
             }
             .to_string()
         );
@@ -273,22 +605,39 @@ mod test {
         assert_eq!(
             t(&input).to_string(),
             quote! {
+                #[cfg_attr(debug_assertions, track_caller)]
                 fn meow(&self, base: String, count: usize) -> String {
-                    if let Some(out) = self.mry.record_call_and_find_mock_output(std::any::Any::type_id(&Self::meow), "Cat::meow", (base.clone(), count.clone())) {
-                        return out;
-                    }
-                    base.repeat(count)
+                    let mry_output = self.mry.record_call_and_find_real_impl(std::any::Any::type_id(&Self::meow), "Cat::meow", (base.clone(), count.clone()), mry::default_output!(String), Some(std::panic::Location::caller()));
+                    mry_output.or_real_impl((base, count), move |#[allow(unused_variables)] (base, count): (String, usize)| -> String {
+                        base.repeat(count)
+                    })
                 }
 
-                pub fn mock_meow<'mry>(&'mry mut self, arg0: impl Into<mry::Matcher<String>>, arg1: impl Into<mry::Matcher<usize>>) -> mry::MockLocator<'mry, (String, usize), String, mry::Behavior2<(String, usize), String> > {
+                pub fn mock_meow<'mry>(&'mry mut self, arg0: impl Into<mry::Matcher<String>>, arg1: impl Into<mry::Matcher<usize>>) -> mry::MockLocator<'mry, (String, usize), String, mry::Behavior2<(String, usize), String>, CatMeowCall> {
                     mry::MockLocator {
                         mocks: self.mry.mocks_write(),
                         key: std::any::Any::type_id(&Self::meow),
                         name: "Cat::meow",
+                        params: &["base", "count"],
                         matcher: Some((arg0.into(), arg1.into(),).into()),
+                        options: Default::default(),
                         _phantom: Default::default(),
                     }
                 }
+
+                #[doc = " Arguments of a call to `Cat::meow`"]
+                #[allow(dead_code)]
+                #[derive(Debug, Clone)]
+                pub struct CatMeowCall {
+                    pub base: String,
+                    pub count: usize,
+                }
+
+                impl From<(String, usize)> for CatMeowCall {
+                    fn from((base, count): (String, usize)) -> Self {
+                        Self { base, count }
+                    }
+                }
             }
             .to_string()
         );
@@ -306,23 +655,44 @@ mod test {
         assert_eq!(
             t(&input).to_string(),
             quote! {
+                #[cfg_attr(debug_assertions, track_caller)]
                 fn meow(&self, out: &'static mut String, base: &str, count: &usize) -> () {
-                    if let Some(out) = self.mry.record_call_and_find_mock_output(std::any::Any::type_id(&Self::meow), "Cat::meow", (out.clone(), base.to_string(), count.clone())) {
-                        return out;
+                    let mut mry_input = (out.clone(), base.to_string(), count.clone());
+                    if let Some(mry_out) = self.mry.record_call_and_find_mock_output_mut(std::any::Any::type_id(&Self::meow), "Cat::meow", &mut mry_input, mry::default_output!(()), Some(std::panic::Location::caller())) {
+                        let (mry_written0, _, _) = mry_input;
+                        *out = mry_written0;
+                        return mry_out;
                     }
                     *out = base.repeat(count);
                 }
 
                 pub fn mock_meow<'mry>(&'mry mut self, arg0: impl Into <mry::Matcher<String>>, arg1: impl Into<mry::Matcher<String>>, arg2: impl Into<mry::Matcher<usize>>)
-                    -> mry::MockLocator<'mry, (String, String, usize), (), mry::Behavior3<(String, String, usize), ()> > {
+                    -> mry::MockLocator<'mry, (String, String, usize), (), mry::Behavior3<(String, String, usize), ()>, CatMeowCall> {
                     mry::MockLocator {
                         mocks: self.mry.mocks_write(),
                         key: std::any::Any::type_id(&Self::meow),
                         name: "Cat::meow",
+                        params: &["out", "base", "count"],
                         matcher: Some((arg0.into(), arg1.into(), arg2.into(),).into()),
+                        options: Default::default(),
                         _phantom: Default::default(),
                     }
                 }
+
+                #[doc = " Arguments of a call to `Cat::meow`"]
+                #[allow(dead_code)]
+                #[derive(Debug, Clone)]
+                pub struct CatMeowCall {
+                    pub out: String,
+                    pub base: String,
+                    pub count: usize,
+                }
+
+                impl From<(String, String, usize)> for CatMeowCall {
+                    fn from((out, base, count): (String, String, usize)) -> Self {
+                        Self { out, base, count }
+                    }
+                }
             }
             .to_string()
         );
@@ -341,21 +711,193 @@ mod test {
             t(&input).to_string(),
             quote! {
                 async fn meow(&self, count: usize) -> String {
-                    if let Some(out) = self.mry.record_call_and_find_mock_output(std::any::Any::type_id(&Self::meow), "Cat::meow", (count.clone())) {
-                        return out;
-                    }
-                    base().await.repeat(count);
+                    let mry_output = self.mry.record_call_and_find_real_impl(std::any::Any::type_id(&Self::meow), "Cat::meow", (count.clone()), mry::default_output!(String), None);
+                    mry_output.or_real_impl_async((count), move |#[allow(unused_variables)] (count): (usize)| async move {
+                        base().await.repeat(count);
+                    }).await
                 }
 
-                pub fn mock_meow<'mry>(&'mry mut self, arg0: impl Into<mry::Matcher<usize>>) -> mry::MockLocator<'mry, (usize), String, mry::Behavior1<(usize), String> > {
+                pub fn mock_meow<'mry>(&'mry mut self, arg0: impl Into<mry::Matcher<usize>>) -> mry::MockLocator<'mry, (usize), String, mry::Behavior1<(usize), String>, CatMeowCall> {
                     mry::MockLocator {
                         mocks: self.mry.mocks_write(),
                         key: std::any::Any::type_id(&Self::meow),
                         name: "Cat::meow",
+                        params: &["count"],
                         matcher: Some((arg0.into(),).into()),
+                        options: Default::default(),
                         _phantom: Default::default(),
                     }
                 }
+
+                #[doc = " Arguments of a call to `Cat::meow`"]
+                #[allow(dead_code)]
+                #[derive(Debug, Clone)]
+                pub struct CatMeowCall {
+                    pub count: usize,
+                }
+
+                impl From<(usize)> for CatMeowCall {
+                    fn from((count): (usize)) -> Self {
+                        Self { count }
+                    }
+                }
+            }
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn writes_back_mut_reference() {
+        let input: ImplItemMethod = parse2(quote! {
+            fn read(&mut self, buf: &mut Vec<u8>, count: usize) -> usize {
+                count
+            }
+        })
+        .unwrap();
+
+        assert_eq!(
+            t(&input).to_string(),
+            quote! {
+                #[cfg_attr(debug_assertions, track_caller)]
+                fn read(&mut self, buf: &mut Vec<u8>, count: usize) -> usize {
+                    let mut mry_input = (buf.clone(), count.clone());
+                    if let Some(mry_out) = self.mry.record_call_and_find_mock_output_mut(std::any::Any::type_id(&Self::read), "Cat::read", &mut mry_input, mry::default_output!(usize), Some(std::panic::Location::caller())) {
+                        let (mry_written0, _) = mry_input;
+                        *buf = mry_written0;
+                        return mry_out;
+                    }
+                    count
+                }
+
+                pub fn mock_read<'mry>(&'mry mut self, arg0: impl Into<mry::Matcher<Vec<u8> >>, arg1: impl Into<mry::Matcher<usize>>) -> mry::MockLocator<'mry, (Vec<u8>, usize), usize, mry::Behavior2<(Vec<u8>, usize), usize>, CatReadCall> {
+                    mry::MockLocator {
+                        mocks: self.mry.mocks_write(),
+                        key: std::any::Any::type_id(&Self::read),
+                        name: "Cat::read",
+                        params: &["buf", "count"],
+                        matcher: Some((arg0.into(), arg1.into(),).into()),
+                        options: Default::default(),
+                        _phantom: Default::default(),
+                    }
+                }
+
+                #[doc = " Arguments of a call to `Cat::read`"]
+                #[allow(dead_code)]
+                #[derive(Debug, Clone)]
+                pub struct CatReadCall {
+                    pub buf: Vec<u8>,
+                    pub count: usize,
+                }
+
+                impl From<(Vec<u8>, usize)> for CatReadCall {
+                    fn from((buf, count): (Vec<u8>, usize)) -> Self {
+                        Self { buf, count }
+                    }
+                }
+            }
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn writes_back_mut_slice() {
+        let input: ImplItemMethod = parse2(quote! {
+            async fn read(&self, buf: &mut [u8]) -> usize {
+                0
+            }
+        })
+        .unwrap();
+
+        assert_eq!(
+            t(&input).to_string(),
+            quote! {
+                async fn read(&self, buf: &mut [u8]) -> usize {
+                    let mut mry_input = (buf.to_vec());
+                    let mry_out = self.mry.record_call_and_find_mock_future_mut(std::any::Any::type_id(&Self::read), "Cat::read", &mut mry_input, mry::default_output!(usize), None);
+                    if let Some(mry_out) = mry_out {
+                        let (mry_written0) = mry_input;
+                        for (to, from) in buf.iter_mut().zip(mry_written0) {
+                            *to = from;
+                        }
+                        return mry_out.await;
+                    }
+                    0
+                }
+
+                pub fn mock_read<'mry>(&'mry mut self, arg0: impl Into<mry::Matcher<Vec<u8> >>) -> mry::MockLocator<'mry, (Vec<u8>), usize, mry::Behavior1<(Vec<u8>), usize>, CatReadCall> {
+                    mry::MockLocator {
+                        mocks: self.mry.mocks_write(),
+                        key: std::any::Any::type_id(&Self::read),
+                        name: "Cat::read",
+                        params: &["buf"],
+                        matcher: Some((arg0.into(),).into()),
+                        options: Default::default(),
+                        _phantom: Default::default(),
+                    }
+                }
+
+                #[doc = " Arguments of a call to `Cat::read`"]
+                #[allow(dead_code)]
+                #[derive(Debug, Clone)]
+                pub struct CatReadCall {
+                    pub buf: Vec<u8>,
+                }
+
+                impl From<(Vec<u8>)> for CatReadCall {
+                    fn from((buf): (Vec<u8>)) -> Self {
+                        Self { buf }
+                    }
+                }
+            }
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn moves_mut_to_real_impl() {
+        let input: ImplItemMethod = parse2(quote! {
+            fn meow(&self, mut count: usize) -> usize {
+                count += 1;
+                count
+            }
+        })
+        .unwrap();
+
+        assert_eq!(
+            t(&input).0.to_string(),
+            quote! {
+                #[cfg_attr(debug_assertions, track_caller)]
+                fn meow(&self, count: usize) -> usize {
+                    let mry_output = self.mry.record_call_and_find_real_impl(std::any::Any::type_id(&Self::meow), "Cat::meow", (count.clone()), mry::default_output!(usize), Some(std::panic::Location::caller()));
+                    mry_output.or_real_impl((count), move |#[allow(unused_variables)] (mut count): (usize)| -> usize {
+                        count += 1;
+                        count
+                    })
+                }
+            }
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn inlines_body_with_references() {
+        let input: ImplItemMethod = parse2(quote! {
+            fn meow(&self, base: &str) -> String {
+                base.to_string()
+            }
+        })
+        .unwrap();
+
+        assert_eq!(
+            t(&input).0.to_string(),
+            quote! {
+                #[cfg_attr(debug_assertions, track_caller)]
+                fn meow(&self, base: &str) -> String {
+                    if let Some(out) = self.mry.record_call_and_find_mock_output(std::any::Any::type_id(&Self::meow), "Cat::meow", (base.to_string()), mry::default_output!(String), Some(std::panic::Location::caller())) {
+                        return out;
+                    }
+                    base.to_string()
+                }
             }
             .to_string()
         );
@@ -373,29 +915,143 @@ mod test {
         assert_eq!(
             t(&input).to_string(),
             quote! {
+				#[cfg_attr(debug_assertions, track_caller)]
 				fn meow(&self, arg0: A, count: usize, arg2: String) -> String {
-                    if let Some(out) = self.mry.record_call_and_find_mock_output(std::any::Any::type_id(&Self::meow), "Cat::meow", (arg0.clone(), count.clone(), arg2.clone())) {
-                        return out;
-                    }
-					let A { name } = arg0;
-					let _ = arg2;
-                    name.repeat(count)
+                    let mry_output = self.mry.record_call_and_find_real_impl(std::any::Any::type_id(&Self::meow), "Cat::meow", (arg0.clone(), count.clone(), arg2.clone()), mry::default_output!(String), Some(std::panic::Location::caller()));
+                    mry_output.or_real_impl((arg0, count, arg2), move |#[allow(unused_variables)] (arg0, count, arg2): (A, usize, String)| -> String {
+                        let A { name } = arg0;
+                        let _ = arg2;
+                        name.repeat(count)
+                    })
                 }
 
-                pub fn mock_meow<'mry>(&'mry mut self, arg0: impl Into<mry::Matcher<A>>, arg1: impl Into<mry::Matcher<usize>>, arg2: impl Into<mry::Matcher<String>>) -> mry::MockLocator<'mry, (A, usize, String), String, mry::Behavior3<(A, usize, String), String> > {
+                pub fn mock_meow<'mry>(&'mry mut self, arg0: impl Into<mry::Matcher<A>>, arg1: impl Into<mry::Matcher<usize>>, arg2: impl Into<mry::Matcher<String>>) -> mry::MockLocator<'mry, (A, usize, String), String, mry::Behavior3<(A, usize, String), String>, CatMeowCall> {
                     mry::MockLocator {
                         mocks: self.mry.mocks_write(),
                         key: std::any::Any::type_id(&Self::meow),
                         name: "Cat::meow",
+                        params: &["arg0", "count", "arg2"],
                         matcher: Some((arg0.into(), arg1.into(), arg2.into(),).into()),
+                        options: Default::default(),
+                        _phantom: Default::default(),
+                    }
+                }
+
+                #[doc = " Arguments of a call to `Cat::meow`"]
+                #[allow(dead_code)]
+                #[derive(Debug, Clone)]
+                pub struct CatMeowCall {
+                    pub arg0: A,
+                    pub count: usize,
+                    pub arg2: String,
+                }
+
+                impl From<(A, usize, String)> for CatMeowCall {
+                    fn from((arg0, count, arg2): (A, usize, String)) -> Self {
+                        Self { arg0, count, arg2 }
+                    }
+                }
+            }
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn many_args() {
+        let input: ImplItemMethod = parse2(quote! {
+            fn meow(&self, a: u8, b: u8, c: u8, d: u8, e: u8, f: u8, g: u8, h: u8, i: u8, j: u8, k: u8, l: u8, m: u8) {
+            }
+        })
+        .unwrap();
+
+        assert_eq!(
+            t(&input).to_string(),
+            quote! {
+                #[cfg_attr(debug_assertions, track_caller)]
+                fn meow(&self, a: u8, b: u8, c: u8, d: u8, e: u8, f: u8, g: u8, h: u8, i: u8, j: u8, k: u8, l: u8, m: u8) -> () {
+                    let mry_output = self.mry.record_call_and_find_real_impl(std::any::Any::type_id(&Self::meow), "Cat::meow", (a.clone(), b.clone(), c.clone(), d.clone(), e.clone(), f.clone(), g.clone(), h.clone(), i.clone(), j.clone(), k.clone(), (l.clone(), m.clone())), mry::default_output!(()), Some(std::panic::Location::caller()));
+                    mry_output.or_real_impl((a, b, c, d, e, f, g, h, i, j, k, (l, m)), move |#[allow(unused_variables)] (a, b, c, d, e, f, g, h, i, j, k, (l, m)): (u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, (u8, u8))| -> () {
+                    })
+                }
+
+                #[allow(clippy::too_many_arguments)]
+                pub fn mock_meow<'mry>(&'mry mut self, arg0: impl Into<mry::Matcher<u8>>, arg1: impl Into<mry::Matcher<u8>>, arg2: impl Into<mry::Matcher<u8>>, arg3: impl Into<mry::Matcher<u8>>, arg4: impl Into<mry::Matcher<u8>>, arg5: impl Into<mry::Matcher<u8>>, arg6: impl Into<mry::Matcher<u8>>, arg7: impl Into<mry::Matcher<u8>>, arg8: impl Into<mry::Matcher<u8>>, arg9: impl Into<mry::Matcher<u8>>, arg10: impl Into<mry::Matcher<u8>>, arg11: impl Into<mry::Matcher<u8>>, arg12: impl Into<mry::Matcher<u8>>)
+                    -> mry::MockLocator<'mry, (u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, (u8, u8)), (), mry::Behavior13<(u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, (u8, u8)), ()>, CatMeowCall> {
+                    mry::MockLocator {
+                        mocks: self.mry.mocks_write(),
+                        key: std::any::Any::type_id(&Self::meow),
+                        name: "Cat::meow",
+                        params: &["a", "b", "c", "d", "e", "f", "g", "h", "i", "j", "k", "l", "m"],
+                        matcher: Some((arg0.into(), arg1.into(), arg2.into(), arg3.into(), arg4.into(), arg5.into(), arg6.into(), arg7.into(), arg8.into(), arg9.into(), arg10.into(), arg11.into(), arg12.into(),).into()),
+                        options: Default::default(),
                         _phantom: Default::default(),
                     }
                 }
+
+                #[doc = " Arguments of a call to `Cat::meow`"]
+                #[allow(dead_code)]
+                #[derive(Debug, Clone)]
+                pub struct CatMeowCall {
+                    pub a: u8,
+                    pub b: u8,
+                    pub c: u8,
+                    pub d: u8,
+                    pub e: u8,
+                    pub f: u8,
+                    pub g: u8,
+                    pub h: u8,
+                    pub i: u8,
+                    pub j: u8,
+                    pub k: u8,
+                    pub l: u8,
+                    pub m: u8,
+                }
+
+                impl From<(u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, (u8, u8))> for CatMeowCall {
+                    fn from((a, b, c, d, e, f, g, h, i, j, k, (l, m)): (u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, (u8, u8))) -> Self {
+                        Self { a, b, c, d, e, f, g, h, i, j, k, l, m }
+                    }
+                }
             }
             .to_string()
         );
     }
 
+    #[test]
+    fn too_many_args() {
+        let input: ImplItemMethod = parse2(quote! {
+            fn meow(&self, a: u8, b: u8, c: u8, d: u8, e: u8, f: u8, g: u8, h: u8, i: u8, j: u8, k: u8, l: u8, m: u8, n: u8, o: u8, p: u8, q: u8) {
+            }
+        })
+        .unwrap();
+
+        assert!(t(&input).0.to_string().starts_with(
+            "compile_error ! (\"mry supports up to 16 arguments, but `meow` has 17\")"
+        ));
+    }
+
+    #[test]
+    fn calls_with_generic_args_are_tuples() {
+        let input: ImplItemMethod = parse2(quote! {
+            fn meow<T: Clone>(&self, value: T, other: &Self) {
+            }
+        })
+        .unwrap();
+
+        let (_, mock, call) = t(&input);
+        assert!(call.is_empty());
+        assert!(mock
+            .to_string()
+            .contains("mry :: Behavior2 < (T , Self) , () > >"));
+    }
+
+    #[test]
+    fn upper_camel_case_method_names() {
+        assert_eq!(upper_camel_case("meow"), "Meow");
+        assert_eq!(upper_camel_case("meow_loudly"), "MeowLoudly");
+        assert_eq!(upper_camel_case("r#type"), "Type");
+    }
+
     #[test]
     fn respect_visibility() {
         let input: ImplItemMethod = parse2(quote! {
@@ -408,11 +1064,37 @@ mod test {
         assert_eq!(
             t(&input).0.to_string(),
             quote! {
+                #[cfg_attr(debug_assertions, track_caller)]
                 pub fn meow(&self, count: usize) -> String {
-                    if let Some(out) = self.mry.record_call_and_find_mock_output(std::any::Any::type_id(&Self::meow), "Cat::meow", (count.clone())) {
-                        return out;
-                    }
-                    "meow".repeat(count)
+                    let mry_output = self.mry.record_call_and_find_real_impl(std::any::Any::type_id(&Self::meow), "Cat::meow", (count.clone()), mry::default_output!(String), Some(std::panic::Location::caller()));
+                    mry_output.or_real_impl((count), move |#[allow(unused_variables)] (count): (usize)| -> String {
+                        "meow".repeat(count)
+                    })
+                }
+            }
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn keeps_track_caller() {
+        let input: ImplItemMethod = parse2(quote! {
+            #[track_caller]
+            fn meow(&self, count: usize) -> String {
+                "meow".repeat(count)
+            }
+        })
+        .unwrap();
+
+        assert_eq!(
+            t(&input).0.to_string(),
+            quote! {
+                #[track_caller]
+                fn meow(&self, count: usize) -> String {
+                    let mry_output = self.mry.record_call_and_find_real_impl(std::any::Any::type_id(&Self::meow), "Cat::meow", (count.clone()), mry::default_output!(String), Some(std::panic::Location::caller()));
+                    mry_output.or_real_impl((count), move |#[allow(unused_variables)] (count): (usize)| -> String {
+                        "meow".repeat(count)
+                    })
                 }
             }
             .to_string()
@@ -3,6 +3,23 @@ use quote::quote;
 use syn::{ExprStruct, Member};
 
 pub(crate) fn transform(input: ExprStruct) -> TokenStream {
+    transform_with_mry(input, quote![Default::default()])
+}
+
+pub(crate) fn transform_spy(input: ExprStruct) -> TokenStream {
+    transform_with_mry(
+        input,
+        quote! {
+            {
+                let mut mry = mry::Mry::default();
+                mry.spy();
+                mry
+            }
+        },
+    )
+}
+
+fn transform_with_mry(input: ExprStruct, mry: TokenStream) -> TokenStream {
     let ident = input.path.clone();
     let mut fields: Vec<_> = input
         .fields
@@ -19,7 +36,7 @@ pub(crate) fn transform(input: ExprStruct) -> TokenStream {
         })
         .collect();
     fields.push(quote! {
-        mry: Default::default(),
+        mry: #mry,
     });
     quote! {
         #ident {
@@ -76,4 +93,29 @@ mod test {
             .to_string()
         );
     }
+
+    #[test]
+    fn adds_spying_mry() {
+        let input: ExprStruct = parse2(quote! {
+            Cat {
+                name: "aaa",
+            }
+        })
+        .unwrap();
+
+        assert_eq!(
+            transform_spy(input).to_string(),
+            quote! {
+                Cat {
+                    name: "aaa",
+                    mry: {
+                        let mut mry = mry::Mry::default();
+                        mry.spy();
+                        mry
+                    },
+                }
+            }
+            .to_string()
+        );
+    }
 }
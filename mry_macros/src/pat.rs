@@ -0,0 +1,80 @@
+use proc_macro2::{TokenStream, TokenTree};
+use quote::quote;
+
+pub(crate) fn transform(input: TokenStream) -> TokenStream {
+    // Split into the pattern and the guard at the top-level `if`
+    let mut tokens = input.clone().into_iter();
+    let pat: TokenStream = tokens
+        .by_ref()
+        .take_while(|token| !matches!(token, TokenTree::Ident(ident) if ident == "if"))
+        .collect();
+    let guard: TokenStream = tokens.collect();
+    let guard = if guard.is_empty() {
+        TokenStream::default()
+    } else {
+        quote![if #guard]
+    };
+    // Matching on the reference binds by reference, so bindings don't move out of the argument
+    quote! {
+        mry::Matcher::Composite(Box::new(mry::matchers::Predicate::new(
+            concat!("Pat(", stringify!(#input), ")"),
+            |input: &_| {
+                #[allow(unused_variables)]
+                let matches = match input {
+                    #pat #guard => true,
+                    _ => false,
+                };
+                matches
+            },
+        )))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn pattern() {
+        assert_eq!(
+            transform(quote![Some(Order { id: 7, .. })]).to_string(),
+            quote! {
+                mry::Matcher::Composite(Box::new(mry::matchers::Predicate::new(
+                    concat!("Pat(", stringify!(Some(Order { id: 7, .. })), ")"),
+                    |input: &_| {
+                        #[allow(unused_variables)]
+                        let matches = match input {
+                            Some(Order { id: 7, .. }) => true,
+                            _ => false,
+                        };
+                        matches
+                    },
+                )))
+            }
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn pattern_with_guard() {
+        assert_eq!(
+            transform(quote![Cmd::Move(x, _) if *x > 0]).to_string(),
+            quote! {
+                mry::Matcher::Composite(Box::new(mry::matchers::Predicate::new(
+                    concat!("Pat(", stringify!(Cmd::Move(x, _) if *x > 0), ")"),
+                    |input: &_| {
+                        #[allow(unused_variables)]
+                        let matches = match input {
+                            Cmd::Move(x, _) if *x > 0 => true,
+                            _ => false,
+                        };
+                        matches
+                    },
+                )))
+            }
+            .to_string()
+        );
+    }
+}
@@ -0,0 +1,49 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+
+/// std implements traits such as `Clone` and `Debug` only for tuples up to this length
+const MAX_TUPLE_LEN: usize = 12;
+
+/// Makes a tuple of the items, nesting the trailing items if they don't fit in a tuple
+/// that std implements traits for. One item is not wrapped in a tuple.
+pub(crate) fn tuple(items: &[TokenStream]) -> TokenStream {
+    if items.len() <= MAX_TUPLE_LEN {
+        return quote![(#(#items),*)];
+    }
+    let (head, tail) = items.split_at(MAX_TUPLE_LEN - 1);
+    let tail = tuple(tail);
+    quote![(#(#head,)* #tail)]
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn items(len: usize) -> Vec<TokenStream> {
+        (0..len)
+            .map(|index| {
+                let ident = quote::format_ident!("a{}", index);
+                quote![#ident]
+            })
+            .collect()
+    }
+
+    #[test]
+    fn flat() {
+        assert_eq!(tuple(&items(1)).to_string(), quote![(a0)].to_string());
+        assert_eq!(
+            tuple(&items(12)).to_string(),
+            quote![(a0, a1, a2, a3, a4, a5, a6, a7, a8, a9, a10, a11)].to_string()
+        );
+    }
+
+    #[test]
+    fn nested() {
+        assert_eq!(
+            tuple(&items(13)).to_string(),
+            quote![(a0, a1, a2, a3, a4, a5, a6, a7, a8, a9, a10, (a11, a12))].to_string()
+        );
+    }
+}